@@ -2,7 +2,10 @@
 
 mod rust_gen;
 mod svd_util;
-use crate::rust_gen::{GenPkgSettings, generate_rust_package};
+use crate::rust_gen::{
+    GenPkgSettings, diff_svd, generate_c_header, generate_docs, generate_normalized_svd,
+    generate_rust_package, lint_svd,
+};
 use clap::{Parser, ValueEnum};
 use env_logger::Env;
 use log::{error, info, warn};
@@ -31,9 +34,22 @@ pub enum Target {
     CortexM,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum Emit {
+    /// Peripheral access crate
+    Rust,
+    /// CMSIS-style C header `<device name>.h`
+    CHeader,
+    /// HTML register manual in folder `docs`
+    Docs,
+}
+
 /// Generate peripheral access crate from SVD file
 #[derive(Parser, Debug)]
 #[command(author, version=env!("CARGO_PKG_VERSION"), about="Tool to generate peripheral access crate from SVD file", long_about = None)]
+#[command(
+    after_help = "Other commands:\n  svd2pac diff <OLD> <NEW>  Compare two SVD files, see `svd2pac diff -h`\n  svd2pac lint <SVD>        Check SVD file without generating code, see `svd2pac lint -h`"
+)]
 pub struct Args {
     /// Disable formatting of generated code using rustfmt mainly for debugging
     #[arg(long,value_parser=clap::value_parser!(bool),default_value_t=false)]
@@ -41,24 +57,88 @@ pub struct Args {
     /// Register description file
     #[arg(value_parser=clap::value_parser!(PathBuf))]
     pub register_description_file_name: PathBuf,
-    /// Destination folder of package
+    /// Destination folder of package. Not required if only `--emit-svd` is given.
     #[arg(value_parser=clap::value_parser!(PathBuf))]
-    pub destination_folder: PathBuf,
+    pub destination_folder: Option<PathBuf>,
     //SVD validation level
     #[arg(long,value_enum,default_value_t=SvdValidationLevel::Weak)]
     pub svd_validation_level: SvdValidationLevel,
     /// Architecture target of the PAC.
     #[arg(long,value_enum,default_value_t=Target::Generic)]
     pub target: Target,
+    /// Outputs to generate in destination folder. Can be repeated or comma separated. [default: rust, none if only `--emit-svd` is given]
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub emit: Option<Vec<Emit>>,
+    /// Write the SVD file with expanded arrays, resolved `derivedFrom` and register properties propagated to all registers.
+    #[arg(long,value_parser=clap::value_parser!(PathBuf),default_value=None)]
+    pub emit_svd: Option<PathBuf>,
     /// Enable the generation of a PAC with the tracing interface.
     #[arg(long,value_parser=clap::value_parser!(bool),default_value_t=false)]
     pub tracing: bool,
+    /// Generate bit-band alias accessors for single bit fields. Only applicable to `--target=cortex-m`.
+    #[arg(long,value_parser=clap::value_parser!(bool),default_value_t=false)]
+    pub bit_band: bool,
+    /// Detect companion SET/CLR/TGL registers and generate `set_bits`, `clear_bits` and `toggle_bits` accessors.
+    #[arg(long,value_parser=clap::value_parser!(bool),default_value_t=false)]
+    pub alias_registers: bool,
+    /// Generate `defmt::Format` implementations for register and enumerated values, enabled by optional `defmt` feature of the PAC.
+    #[arg(long,value_parser=clap::value_parser!(bool),default_value_t=false)]
+    pub defmt: bool,
+    /// Generate `serde::Serialize` and `serde::Deserialize` implementations for register and enumerated values, enabled by optional `serde` feature of the PAC.
+    #[arg(long,value_parser=clap::value_parser!(bool),default_value_t=false)]
+    pub serde: bool,
     /// Define package name in toml. Default is name stored in register description file
     #[arg(long,value_parser=clap::value_parser!(String),default_value=None)]
     pub package_name: Option<String>,
     /// Specify a license file whose content is used instead of one defined in SVD.
     #[arg(long,value_parser=clap::value_parser!(PathBuf),default_value=None)]
     pub license_file: Option<PathBuf>,
+    /// Report errors of SVD items and generate code omitting these items instead of failing.
+    #[arg(long,value_parser=clap::value_parser!(bool),default_value_t=false)]
+    pub keep_going: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum ReportFormat {
+    /// One line for each change or diagnostic
+    Text,
+    /// JSON object with list of changes or diagnostics
+    Json,
+}
+
+/// Compare two SVD files
+#[derive(Parser, Debug)]
+#[command(name = "svd2pac diff", version=env!("CARGO_PKG_VERSION"), about="Compare peripherals, registers and bitfields of two SVD files", long_about = None)]
+#[command(after_help = "Exit code is 1 if a change breaks the API of the generated PAC.")]
+pub struct DiffArgs {
+    /// Old register description file
+    #[arg(value_parser=clap::value_parser!(PathBuf))]
+    pub old_register_description_file_name: PathBuf,
+    /// New register description file
+    #[arg(value_parser=clap::value_parser!(PathBuf))]
+    pub new_register_description_file_name: PathBuf,
+    /// Output format
+    #[arg(long,value_enum,default_value_t=ReportFormat::Text)]
+    pub format: ReportFormat,
+    //SVD validation level
+    #[arg(long,value_enum,default_value_t=SvdValidationLevel::Weak)]
+    pub svd_validation_level: SvdValidationLevel,
+}
+
+/// Check SVD file
+#[derive(Parser, Debug)]
+#[command(name = "svd2pac lint", version=env!("CARGO_PKG_VERSION"), about="Check SVD file for problems in generated code without generating code", long_about = None)]
+#[command(after_help = "Exit code is 1 if at least one error is found.")]
+pub struct LintArgs {
+    /// Register description file
+    #[arg(value_parser=clap::value_parser!(PathBuf))]
+    pub register_description_file_name: PathBuf,
+    /// Output format
+    #[arg(long,value_enum,default_value_t=ReportFormat::Text)]
+    pub format: ReportFormat,
+    //SVD validation level
+    #[arg(long,value_enum,default_value_t=SvdValidationLevel::Weak)]
+    pub svd_validation_level: SvdValidationLevel,
 }
 
 /// Main function that parses command line parameters after parsing it invoking [`main`]
@@ -78,11 +158,22 @@ where
     I: IntoIterator<Item = T>,
     T: Into<OsString> + Clone,
 {
-    self::main(Args::parse_from(args));
+    let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+    // Subcommands are dispatched on the first argument to keep the arguments of PAC generation unchanged
+    let exit_code = match args.get(1).and_then(|arg| arg.to_str()) {
+        Some("diff") => main_diff(DiffArgs::parse_from(&args[1..])),
+        Some("lint") => main_lint(LintArgs::parse_from(&args[1..])),
+        _ => {
+            self::main(Args::parse_from(args));
+            0
+        }
+    };
+    if exit_code != 0 {
+        exit(exit_code);
+    }
 }
 
-/// Convert SVD file to PAC
-pub fn main(args: Args) {
+fn init_logger() {
     // Use
     let env = Env::default()
         .filter_or("SVD2PAC_LOG_LEVEL", "info")
@@ -93,35 +184,149 @@ pub fn main(args: Args) {
     if let Err(error) = env_logger::try_init_from_env(env) {
         warn!("{}", error);
     }
+}
+
+/// Compare two SVD files and print the changes on standard output.
+///
+/// Return exit code: 0 if no change breaks the API of generated PAC, 1 otherwise.
+pub fn main_diff(args: DiffArgs) -> i32 {
+    init_logger();
+    let diff = match diff_svd(
+        &args.old_register_description_file_name,
+        &args.new_register_description_file_name,
+        args.svd_validation_level,
+    ) {
+        Ok(diff) => diff,
+        Err(err) => {
+            error!("Failed to compare SVD files with err {}", err);
+            exit(-1);
+        }
+    };
+    match args.format {
+        ReportFormat::Text => println!("{diff}"),
+        ReportFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&diff).expect("Failed to serialize diff")
+        ),
+    }
+    if diff.breaking { 1 } else { 0 }
+}
+
+/// Check SVD file and print the diagnostics on standard output.
+///
+/// Return exit code: 0 if no error is found, 1 otherwise.
+pub fn main_lint(args: LintArgs) -> i32 {
+    init_logger();
+    let report = match lint_svd(
+        &args.register_description_file_name,
+        args.svd_validation_level,
+    ) {
+        Ok(report) => report,
+        Err(err) => {
+            error!("Failed to check SVD file with err {}", err);
+            exit(-1);
+        }
+    };
+    match args.format {
+        ReportFormat::Text => println!("{report}"),
+        ReportFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("Failed to serialize lint report")
+        ),
+    }
+    if report.errors > 0 { 1 } else { 0 }
+}
+
+/// Convert SVD file to PAC
+pub fn main(args: Args) {
+    init_logger();
 
     info!(
         "Reading register description file {}",
         args.register_description_file_name.to_str().unwrap()
     );
-    let destination_folder = args.destination_folder;
+    // Only the normalized SVD is written if it is requested without destination folder
+    let emit = args.emit.unwrap_or_else(|| {
+        if args.destination_folder.is_none() && args.emit_svd.is_some() {
+            Vec::new()
+        } else {
+            vec![Emit::Rust]
+        }
+    });
+    let destination_folder = match args.destination_folder {
+        Some(destination_folder) => destination_folder,
+        None if emit.is_empty() => PathBuf::new(),
+        None => {
+            error!("Destination folder is required unless only --emit-svd is given");
+            exit(-1);
+        }
+    };
 
-    if !destination_folder.exists() {
-        info!("Create folder {}", &destination_folder.to_str().unwrap());
+    if !emit.is_empty() && !destination_folder.exists() {
+        info!("Create folder {}", destination_folder.to_str().unwrap());
         if let Err(err) = fs::create_dir_all(&destination_folder) {
             error!("Failed to create destination folder: {}", err);
             exit(-1);
         };
     }
 
-    if let Err(err) = generate_rust_package(
-        &args.register_description_file_name,
-        &destination_folder,
-        GenPkgSettings {
-            run_rustfmt: !args.disable_rust_fmt,
-            svd_validation_level: args.svd_validation_level,
-            target: args.target,
-            tracing: args.tracing,
-            package_name: args.package_name,
-            license_file: args.license_file,
-            svd2pac_version: VERSION.to_owned(),
-        },
-    ) {
-        error!("Failed to generate code with err {}", err);
-        exit(-1);
+    if args.bit_band && args.target != Target::CortexM {
+        warn!("--bit-band is supported only with --target=cortex-m. Option ignored");
+    }
+
+    let settings = GenPkgSettings {
+        run_rustfmt: !args.disable_rust_fmt,
+        svd_validation_level: args.svd_validation_level,
+        target: args.target,
+        tracing: args.tracing,
+        bit_band: args.bit_band && args.target == Target::CortexM,
+        alias_registers: args.alias_registers,
+        defmt: args.defmt,
+        serde: args.serde,
+        package_name: args.package_name,
+        license_file: args.license_file,
+        svd2pac_version: VERSION.to_owned(),
+        keep_going: args.keep_going,
+    };
+
+    if emit.contains(&Emit::Rust) {
+        if let Err(err) = generate_rust_package(
+            &args.register_description_file_name,
+            &destination_folder,
+            &settings,
+        ) {
+            error!("Failed to generate code with err {}", err);
+            exit(-1);
+        }
+    }
+
+    if emit.contains(&Emit::CHeader) {
+        if let Err(err) = generate_c_header(
+            &args.register_description_file_name,
+            &destination_folder,
+            &settings,
+        ) {
+            error!("Failed to generate C header with err {}", err);
+            exit(-1);
+        }
+    }
+
+    if emit.contains(&Emit::Docs) {
+        if let Err(err) = generate_docs(
+            &args.register_description_file_name,
+            &destination_folder,
+            &settings,
+        ) {
+            error!("Failed to generate register manual with err {}", err);
+            exit(-1);
+        }
+    }
+    if let Some(svd_path) = &args.emit_svd {
+        if let Err(err) =
+            generate_normalized_svd(&args.register_description_file_name, svd_path, &settings)
+        {
+            error!("Failed to generate normalized SVD with err {}", err);
+            exit(-1);
+        }
     }
 }
@@ -1,4 +1,8 @@
+mod c_header;
+mod diff;
+mod docs;
 mod ir;
+mod lint;
 mod util;
 mod xml2ir;
 use std::{
@@ -7,18 +11,21 @@ use std::{
 };
 
 use self::util::ToSanitizedSymbol;
-use crate::{SvdValidationLevel, Target};
+use crate::{SvdValidationLevel, Target, svd_util};
 use anyhow::{Context, Result, anyhow};
+use indexmap::IndexMap;
 use lazy_regex::regex;
 use log::{error, info, warn};
+use serde::Serialize;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Write;
 use std::fs;
 use std::fs::File;
 use std::io::Read;
-use std::io::{BufRead, BufReader};
 use std::process::Command;
+use std::rc::Rc;
 use tera::{Tera, Value, to_value, try_get_value};
 
 /// Convert [`Vec<PathChunk>`] to a string representation of a register path.
@@ -183,6 +190,17 @@ fn filter_svd_description_to_doc(
     }
 }
 
+/// Escape a string so it can be used as content of a Rust string literal
+fn filter_escape_str(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    if let Value::String(string) = value {
+        Ok(Value::String(string.escape_debug().to_string()))
+    } else {
+        Err(tera::Error::msg(format!(
+            "escape_str only supports String as argument. value:{value}"
+        )))
+    }
+}
+
 fn execute_template(
     tera: &Tera,
     template_name: &str,
@@ -226,10 +244,22 @@ fn get_tera_instance() -> anyhow::Result<Tera> {
             "reg_name.tera",
             include_str!("../templates/rust/reg_name.tera"),
         ),
+        (
+            "register_file.tera",
+            include_str!("../templates/rust/register_file.tera"),
+        ),
+        (
+            "trace_file.tera",
+            include_str!("../templates/rust/trace_file.tera"),
+        ),
         (
             "peri_mod.tera",
             include_str!("../templates/rust/peri_mod.tera"),
         ),
+        (
+            "metadata.tera",
+            include_str!("../templates/rust/metadata.tera"),
+        ),
         (
             "aurix_core.tera",
             include_str!("../templates/rust/aurix_core.tera"),
@@ -244,6 +274,20 @@ fn get_tera_instance() -> anyhow::Result<Tera> {
             "device_x.tera",
             include_str!("../templates/rust/device_x.tera"),
         ),
+        ("c_header.tera", include_str!("../templates/c/header.tera")),
+        ("c_macros.tera", include_str!("../templates/c/macros.tera")),
+        (
+            "docs_index.tera",
+            include_str!("../templates/docs/index.tera"),
+        ),
+        (
+            "docs_peripheral.tera",
+            include_str!("../templates/docs/peripheral.tera"),
+        ),
+        (
+            "docs_style.tera",
+            include_str!("../templates/docs/style.tera"),
+        ),
     ])?;
     Ok(tera)
 }
@@ -256,68 +300,24 @@ fn get_xml_string(path: &Path, xml: &mut String) -> Result<()> {
     Ok(())
 }
 
-// check if vendor extension is present in the svd
-fn check_for_vendor_extension(path: &Path) -> Result<bool> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    let mut present = false;
-    for line in reader.lines() {
-        match line? {
-            s if s.contains("vendorExtensions") => present = true,
-            _ => continue,
-        }
-    }
-    Ok(present)
-}
-
-fn error_with_context() -> Result<()> {
-    Err(anyhow!("svd parsing error")).context("problem with svd to extract aurix cpu related peripherals, <vendorExtensions> or <aurixCSFR> maybe missing?")
-}
-
-/// Extracts the AURIX CSFR SVD section from the given XML file and constructs a new SVD string.
-///
-/// # Arguments
-///
-/// * `path` - The path to the XML file containing the SVD.
-/// * `svd_string` - A mutable reference to a string where the extracted SVD will be stored.
-///
-/// # Returns
-///
-/// * `Result<()>` - Returns an Ok(()) if successful, or an error if there was a problem reading the file or parsing the XML.
-pub fn get_aurix_csfr_svd(path: &Path, svd_string: &mut String) -> Result<()> {
-    let xml = &mut String::new();
-    File::open(path)
-        .context("Cannot open register description file")?
-        .read_to_string(xml)
-        .context("Cannot read register description file")?;
-
-    let common_svd_tags: Vec<&str> = xml.split("<peripherals>").collect();
-    let svd_split_vec: Vec<&str> = xml.split("<aurixCSFR>").collect();
-    let mut extended_peripherals: Vec<&str> = Vec::new();
-    if svd_split_vec.len() > 1 {
-        extended_peripherals = svd_split_vec[1].split("</aurixCSFR>").collect();
-    } else {
-        error_with_context()?;
-    }
-    *svd_string = format!(
-        "{} {} {} {} {}",
-        common_svd_tags[0],
-        "<peripherals>",
-        extended_peripherals[0],
-        "</peripherals>\n",
-        "</device>"
-    );
-    Ok(())
-}
-
 pub struct GenPkgSettings {
     pub run_rustfmt: bool,
     pub svd_validation_level: SvdValidationLevel,
     pub target: Target,
     pub tracing: bool,
+    /// Generate bit-band alias accessors for single bit fields (Cortex-M only)
+    pub bit_band: bool,
+    /// Detect companion SET/CLR/TGL registers and generate accessors
+    pub alias_registers: bool,
+    /// Generate `defmt::Format` implementations
+    pub defmt: bool,
+    /// Generate `serde::Serialize` and `serde::Deserialize` implementations
+    pub serde: bool,
     pub package_name: Option<String>,
     pub license_file: Option<PathBuf>,
     pub svd2pac_version: String,
+    /// Omit SVD items with errors from generated code instead of failing
+    pub keep_going: bool,
 }
 
 fn precompile_tera(tera: &mut Tera) {
@@ -330,6 +330,7 @@ fn precompile_tera(tera: &mut Tera) {
     tera.register_filter("to_enumerated_const_id", filter_to_const_id);
     tera.register_filter("prepend_lines", filter_prepend_lines);
     tera.register_filter("svd_description_to_doc", filter_svd_description_to_doc);
+    tera.register_filter("escape_str", filter_escape_str);
 }
 
 fn generate_lib_rs_module(
@@ -360,6 +361,7 @@ fn generate_cargo_toml(
 fn generate_tracing_module(
     tera: &Tera,
     ir: &ir::IR,
+    ir_csfr: Option<&ir::IR>,
     destination_folder: &Path,
     context: &tera::Context,
 ) -> anyhow::Result<()> {
@@ -368,6 +370,23 @@ fn generate_tracing_module(
     execute_template(tera, "tracing.tera", context, &lib_path)
         .context("Failed generation of tracing.rs")?;
 
+    // register_file module with the reset state and behaviour of all registers
+    let lib_path = destination_folder.join("src/register_file.rs");
+    let mut register_file_context = context.clone();
+    register_file_context.insert("simulated_registers", &simulated_registers(ir));
+    execute_template(
+        tera,
+        "register_file.tera",
+        &register_file_context,
+        &lib_path,
+    )
+    .context("Failed generation of register_file.rs")?;
+
+    // trace_file module to export and replay register accesses
+    let lib_path = destination_folder.join("src/trace_file.rs");
+    execute_template(tera, "trace_file.tera", context, &lib_path)
+        .context("Failed generation of trace_file.rs")?;
+
     let svd2pac_version = context.get("svd2pac_version").unwrap().as_str();
     let now = context.get("now").unwrap().as_str();
     // reg_name module
@@ -397,15 +416,23 @@ fn generate_tracing_module(
     // A [`phf`](https://crates.io/crate/phf) of physical addresses to
     // associated register names is generated as `reg_name.rs` module.
     //
-    // This is done by flattening the SVD-tree structure into a map
-    // of `Vec<PathChunk>` to `RegisterAbs`, which is then inverted to
-    // a map of `address` to Vec<Vec<PathChunk>>.
+    // This is done by flattening the IR-tree structure into a map
+    // of `address` to Vec<Vec<PathChunk>>. Items omitted because of
+    // errors are not part of the IR and therefore not in the map.
     //
     // This map is passed to the tera module where it is rendered
     // into the respective hash map entries.
+    //
+    // For the reverse lookup the paths are normalized and mapped to
+    // address, size and access of the register in a second hash map.
     let mut context = tera::Context::new();
     let lib_path = destination_folder.join("src/reg_name.rs");
     context.insert("register_addresses", &ir.register_addresses);
+    context.insert("register_locations", &register_locations(ir));
+    // Aurix core special function registers have their own address space
+    if let Some(ir_csfr) = ir_csfr {
+        context.insert("csfr_register_names", &csfr_register_names(ir_csfr));
+    }
     context.insert("ir", &ir);
     context.insert("svd2pac_version", &svd2pac_version);
     context.insert("now", &now);
@@ -414,6 +441,267 @@ fn generate_tracing_module(
     Ok(())
 }
 
+/// Register at an absolute address simulated by `RegisterFile` of the tracing feature.
+///
+/// Masks select the bits with the behaviour of `modifiedWriteValues` and `readAction` in SVD.
+#[derive(Debug, Default, Serialize)]
+struct SimulatedRegister {
+    address: u64,
+    /// Size in bytes
+    size: u32,
+    /// Value after reset. Bits without defined reset value are 0.
+    reset_value: u64,
+    /// Bits updated with the written value
+    write_mask: u64,
+    one_to_clear: u64,
+    one_to_set: u64,
+    one_to_toggle: u64,
+    zero_to_clear: u64,
+    zero_to_set: u64,
+    zero_to_toggle: u64,
+    /// Bits cleared by any write
+    clear_on_write: u64,
+    /// Bits set by any write
+    set_on_write: u64,
+    /// Bits cleared by reading the register
+    clear_on_read: u64,
+    /// Bits set by reading the register
+    set_on_read: u64,
+}
+
+impl SimulatedRegister {
+    /// Add the behaviour of all bitfields of `register` including bitfields inherited by `derivedFrom`.
+    /// Registers without bitfields behave as a single bitfield.
+    fn add_register(&mut self, register: &ir::Register) {
+        let size_mask = register.size.mask();
+        let fields = register.resolved_fields();
+        if fields.is_empty() {
+            self.add_bits(
+                size_mask,
+                register.access != ir::RegisterAccess::R,
+                register.modified_write_values,
+                register.read_action,
+            );
+        }
+        for field in fields.values() {
+            let field = field.borrow();
+            for index in 0..field.dim {
+                let mask = (field.mask as u64) << (field.offset + index * field.dim_increment);
+                self.add_bits(
+                    mask & size_mask,
+                    field.access != ir::RegisterBitfieldAccess::R,
+                    field
+                        .modified_write_values
+                        .unwrap_or(register.modified_write_values),
+                    field.read_action.or(register.read_action),
+                );
+            }
+        }
+    }
+
+    fn add_bits(
+        &mut self,
+        mask: u64,
+        writable: bool,
+        modified_write_values: ir::ModifiedWriteValues,
+        read_action: Option<ir::ReadAction>,
+    ) {
+        if writable {
+            *match modified_write_values {
+                ir::ModifiedWriteValues::Modify => &mut self.write_mask,
+                ir::ModifiedWriteValues::OneToClear => &mut self.one_to_clear,
+                ir::ModifiedWriteValues::OneToSet => &mut self.one_to_set,
+                ir::ModifiedWriteValues::OneToToggle => &mut self.one_to_toggle,
+                ir::ModifiedWriteValues::ZeroToClear => &mut self.zero_to_clear,
+                ir::ModifiedWriteValues::ZeroToSet => &mut self.zero_to_set,
+                ir::ModifiedWriteValues::ZeroToToggle => &mut self.zero_to_toggle,
+                ir::ModifiedWriteValues::Clear => &mut self.clear_on_write,
+                ir::ModifiedWriteValues::Set => &mut self.set_on_write,
+            } |= mask;
+        }
+        match read_action {
+            Some(ir::ReadAction::Clear) => self.clear_on_read |= mask,
+            Some(ir::ReadAction::Set) => self.set_on_read |= mask,
+            // Modifications are device specific and can be simulated with custom behaviours
+            Some(ir::ReadAction::Modify | ir::ReadAction::ModifyExternal) | None => {}
+        }
+    }
+}
+
+/// Collect the registers of all peripherals sorted by address.
+///
+/// Aliased registers are merged in one register with the reset value of the first
+/// alias and the behaviour of the bitfields of all aliases.
+fn simulated_registers(ir: &ir::IR) -> Vec<SimulatedRegister> {
+    let mut simulated_registers = IndexMap::new();
+    for_each_register(ir, &mut |address, register| {
+        simulated_registers
+            .entry(address)
+            .or_insert_with(|| SimulatedRegister {
+                address,
+                size: register.size.bytes(),
+                reset_value: register.reset_value & register.reset_mask,
+                ..Default::default()
+            })
+            .add_register(register);
+    });
+    simulated_registers.sort_keys();
+    simulated_registers.into_values().collect()
+}
+
+/// Call `f` with the absolute address of all registers of all peripherals.
+/// Arrays of peripherals, clusters and registers are expanded.
+fn for_each_register(ir: &ir::IR, f: &mut impl FnMut(u64, &ir::Register)) {
+    for peripheral in ir.device.peripheral_mod.values() {
+        let peripheral = peripheral.borrow();
+        for base_addr in &peripheral.base_addr {
+            walk_registers(&peripheral.registers, &peripheral.clusters, *base_addr, f);
+        }
+    }
+}
+
+fn walk_registers(
+    registers: &IndexMap<String, Rc<RefCell<ir::Register>>>,
+    clusters: &IndexMap<String, Rc<RefCell<ir::Cluster>>>,
+    base_addr: u64,
+    f: &mut impl FnMut(u64, &ir::Register),
+) {
+    for register in registers.values() {
+        let register = register.borrow();
+        for index in 0..register.dim {
+            f(
+                base_addr + register.offset as u64 + (index * register.dim_increment) as u64,
+                &register,
+            );
+        }
+    }
+    for cluster in clusters.values() {
+        let cluster = cluster.borrow();
+        for index in 0..cluster.dim {
+            walk_registers(
+                &cluster.registers,
+                &cluster.clusters,
+                base_addr + cluster.offset as u64 + (index * cluster.dim_increment) as u64,
+                f,
+            );
+        }
+    }
+}
+
+/// Register reachable by a path of `IR::register_addresses` for the reverse lookup in `reg_name` module.
+#[derive(Debug, Serialize)]
+struct RegisterLocation {
+    /// Path normalized as `reg_name::normalize_path` in the generated code
+    key: String,
+    address: u64,
+    /// Size in bytes
+    size: u32,
+    access: ir::RegisterAccess,
+}
+
+/// Normalize a register path to lower case without `()` and raw identifier prefix, e.g. `timer[1].ch[2].cr`.
+fn normalize_register_path(path: &[ir::PathChunk]) -> String {
+    let mut normalized = String::new();
+    for (index, path_chunk) in path.iter().enumerate() {
+        if index > 0 {
+            normalized.push('.');
+        }
+        normalized.push_str(
+            &path_chunk
+                .path
+                .trim_start_matches("r#")
+                .to_ascii_lowercase(),
+        );
+        if let Some(index) = path_chunk.index {
+            let _ = write!(normalized, "[{index}]");
+        }
+    }
+    normalized
+}
+
+/// Collect address, size and access of all paths in `IR::register_addresses`.
+///
+/// Size and access are taken from the register with the name of the last path chunk at
+/// the address. If no name matches, e.g. for registers renamed in the IR, the first register
+/// at the address is used and a warning is logged. Paths of registers omitted from the IR are skipped.
+fn register_locations(ir: &ir::IR) -> Vec<RegisterLocation> {
+    // Accessor name, size in bytes and access of the registers at an address
+    let mut registers_at: IndexMap<u64, Vec<(String, u32, ir::RegisterAccess)>> = IndexMap::new();
+    for_each_register(ir, &mut |address, register| {
+        registers_at.entry(address).or_default().push((
+            register.name.to_sanitized_func_ident(),
+            register.size.bytes(),
+            register.access.clone(),
+        ));
+    });
+    let mut locations = IndexMap::new();
+    for (address, paths) in &ir.register_addresses {
+        let Some(registers) = registers_at.get(address) else {
+            continue;
+        };
+        for path in paths {
+            let register_name = path.last().map(|path_chunk| &path_chunk.path);
+            let (_, size, access) = registers
+                .iter()
+                .find(|(name, _, _)| Some(name) == register_name)
+                .unwrap_or_else(|| {
+                    warn!(
+                        "No register named {} at address {address:#x}, size and access of {} are used",
+                        register_name.map_or("", String::as_str),
+                        registers[0].0
+                    );
+                    &registers[0]
+                });
+            let key = normalize_register_path(path);
+            locations
+                .entry(key.clone())
+                .or_insert_with(|| RegisterLocation {
+                    key,
+                    address: *address,
+                    size: *size,
+                    access: access.clone(),
+                });
+        }
+    }
+    locations.into_values().collect()
+}
+
+/// Map addresses of Aurix core special function registers to their accessor, e.g. `CSFR_CPU.dpr_dpry_l0()`.
+///
+/// Clusters and register arrays of CSFR are unrolled in accessor functions named as in `aurix_core.tera`.
+/// All CPUs share the same addresses and are accessed through `CSFR_CPU`, so duplicated names are removed.
+fn csfr_register_names(ir_csfr: &ir::IR) -> IndexMap<u64, Vec<String>> {
+    let mut names: IndexMap<u64, Vec<String>> = IndexMap::new();
+    for (address, paths) in &ir_csfr.register_addresses {
+        for path in paths {
+            let chunk_name = |chunk: &ir::PathChunk| chunk.path.trim_start_matches("r#").to_owned();
+            let func_name = match path.as_slice() {
+                [_, register] => {
+                    chunk_name(register) + &register.index.map_or(String::new(), |i| i.to_string())
+                }
+                [_, cluster, register] => {
+                    let name = chunk_name(cluster) + "_" + &chunk_name(register);
+                    match (cluster.index, register.index) {
+                        (None, None) => name,
+                        (Some(index), None) | (None, Some(index)) => format!("{name}{index}"),
+                        (Some(cluster_index), Some(register_index)) => {
+                            format!("{name}_{cluster_index}_{register_index}")
+                        }
+                    }
+                }
+                // Nested clusters are not supported for CSFR
+                _ => continue,
+            };
+            let name = format!("CSFR_CPU.{}()", func_name.to_sanitized_func_ident());
+            let address_names = names.entry(*address).or_default();
+            if !address_names.contains(&name) {
+                address_names.push(name);
+            }
+        }
+    }
+    names
+}
+
 fn generate_common_module(
     tera: &Tera,
     _ir: &ir::IR,
@@ -426,13 +714,60 @@ fn generate_common_module(
     Ok(())
 }
 
+fn generate_metadata_module(
+    tera: &Tera,
+    destination_folder: &Path,
+    context: &tera::Context,
+) -> anyhow::Result<()> {
+    let lib_path = destination_folder.join("src/metadata.rs");
+    execute_template(tera, "metadata.tera", context, &lib_path)
+        .context("Failed generation of metadata.rs")?;
+    Ok(())
+}
+
+/// Cortex-M peripheral region that supports bit-banding
+const BIT_BAND_PERIPHERAL_REGION: std::ops::Range<u64> = 0x4000_0000..0x4010_0000;
+
+/// Check if all registers of the peripherals sharing the module `module_id` are mapped in the bit-band region.
+///
+/// Offsets of registers and clusters are considered, so a peripheral starting in the region
+/// with registers beyond its end has no bit-band accessors.
+fn is_bit_band_module(ir: &ir::IR, module_id: &str) -> bool {
+    let mut in_region = true;
+    for peri in ir.device.peripheral_mod.values() {
+        let peri = peri.borrow();
+        if peri.module_id != module_id {
+            continue;
+        }
+        for base_addr in &peri.base_addr {
+            in_region &= BIT_BAND_PERIPHERAL_REGION.contains(base_addr);
+            walk_registers(
+                &peri.registers,
+                &peri.clusters,
+                *base_addr,
+                &mut |address, register| {
+                    let end = address + register.size.bytes() as u64;
+                    in_region &= BIT_BAND_PERIPHERAL_REGION.start <= address
+                        && end <= BIT_BAND_PERIPHERAL_REGION.end;
+                },
+            );
+        }
+    }
+    if !in_region {
+        info!(
+            "Registers of module {module_id} are not in bit-band region. No bit-band accessors generated"
+        );
+    }
+    in_region
+}
+
 fn generate_peripheral_module(
     tera: &Tera,
     ir: &ir::IR,
     template_name: &str,
     destination_folder: &Path,
-    svd2pac_version: &str,
     now: &str,
+    settings: &GenPkgSettings,
 ) -> anyhow::Result<()> {
     // Generate one module for each peripheral
     for (_, peri) in &ir.device.peripheral_mod {
@@ -445,8 +780,14 @@ fn generate_peripheral_module(
         let mut context = tera::Context::new();
         context.insert("peri", peri);
         context.insert("ir", &ir);
-        context.insert("svd2pac_version", svd2pac_version);
+        context.insert("svd2pac_version", &settings.svd2pac_version);
         context.insert("now", now);
+        context.insert(
+            "bit_band",
+            &(settings.bit_band && is_bit_band_module(ir, &module_name)),
+        );
+        context.insert("defmt", &settings.defmt);
+        context.insert("serde", &settings.serde);
         execute_template(
             tera,
             template_name,
@@ -467,9 +808,14 @@ fn generate_aurix_core_ir(
         svd_validation_level,
         target: _,
         tracing: _,
+        bit_band: _,
+        alias_registers: _,
+        defmt: _,
+        serde: _,
         package_name: _,
         license_file,
         svd2pac_version: _,
+        keep_going,
     } = settings;
 
     info!("Start generating csfr rust code");
@@ -481,52 +827,100 @@ fn generate_aurix_core_ir(
                 .with_context(|| format!("Unable to read license file {path:?}"))
         })
         .transpose()?;
-    // If target is aurix, create csfr
-    let result = check_for_vendor_extension(xml_path)?;
-    if result {
-        let svd_csfr_xml = &mut String::with_capacity(500);
-        get_aurix_csfr_svd(xml_path, svd_csfr_xml)?;
-        let mut svd_device = xml2ir::parse_xml(svd_csfr_xml, *svd_validation_level)?;
-        // Rename peripherals
-        for peri in svd_device.peripherals.iter_mut() {
-            peri.name = "csfr_".to_string() + &peri.name
-        }
-        let ir_csfr = xml2ir::svd_device2ir(&svd_device, &custom_license_text)?;
-        Ok(Some(ir_csfr))
-    } else {
-        Ok(None)
+    let xml = &mut String::new();
+    get_xml_string(xml_path, xml)?;
+    let (mut svd_device, vendor_extensions) = xml2ir::parse_xml(xml, *svd_validation_level)
+        .and_then(|svd_device| {
+            let vendor_extensions = xml2ir::parse_vendor_extensions(xml, *svd_validation_level)?;
+            Ok((svd_device, vendor_extensions))
+        })
+        .map_err(|err| xml2ir::add_source_location(err, xml_path, xml))?;
+    if vendor_extensions.aurix_csfr.is_empty() {
+        return Ok(None);
+    }
+    // Core special function registers inherit device properties
+    svd_device.peripherals = vendor_extensions.aurix_csfr;
+    svd_parser::expand_properties(&mut svd_device);
+    // Rename peripherals
+    for peri in svd_device.peripherals.iter_mut() {
+        peri.name = "csfr_".to_string() + &peri.name
+    }
+    if !keep_going {
+        let ir_csfr = xml2ir::svd_device2ir(&svd_device, &custom_license_text, false)?;
+        return Ok(Some(ir_csfr));
+    }
+    let (ir_csfr, mut errors) =
+        xml2ir::svd_device2ir_keep_going(&svd_device, &custom_license_text, false)?;
+    xml2ir::locate_errors(&mut errors, xml_path, xml);
+    report_omitted_items(&errors);
+    Ok(Some(ir_csfr))
+}
+
+/// Parse SVD file and convert it to [`ir::IR`]
+fn read_ir(xml_path: &Path, settings: &GenPkgSettings) -> anyhow::Result<ir::IR> {
+    // Read license file if specified
+    let custom_license_text = settings
+        .license_file
+        .as_ref()
+        .map(|path| {
+            fs::read_to_string(path)
+                .with_context(|| format!("Unable to read license file {path:?}"))
+        })
+        .transpose()?;
+
+    let xml = &mut String::new();
+    get_xml_string(xml_path, xml)?;
+    let svd_device = xml2ir::parse_xml(xml, settings.svd_validation_level)
+        .map_err(|err| xml2ir::add_source_location(err, xml_path, xml))?;
+    if !settings.keep_going {
+        return xml2ir::svd_device2ir(&svd_device, &custom_license_text, settings.alias_registers)
+            .map_err(|err| xml2ir::add_source_location(err, xml_path, xml));
+    }
+    let (ir, mut errors) = xml2ir::svd_device2ir_keep_going(
+        &svd_device,
+        &custom_license_text,
+        settings.alias_registers,
+    )?;
+    xml2ir::locate_errors(&mut errors, xml_path, xml);
+    report_omitted_items(&errors);
+    Ok(ir)
+}
+
+/// Log errors of SVD items omitted from generated code.
+fn report_omitted_items(errors: &[svd_util::ParseError]) {
+    for err in errors {
+        error!("{err}");
+    }
+    if !errors.is_empty() {
+        warn!(
+            "{} SVD items with errors are omitted from generated code",
+            errors.len()
+        );
     }
 }
 
 pub(crate) fn generate_rust_package(
     xml_path: &Path,
     destination_folder: &Path,
-    settings: GenPkgSettings,
+    settings: &GenPkgSettings,
 ) -> anyhow::Result<()> {
     let GenPkgSettings {
         run_rustfmt,
-        svd_validation_level,
+        svd_validation_level: _,
         target,
         tracing,
+        bit_band,
+        alias_registers,
+        defmt,
+        serde,
         ref package_name,
-        ref license_file,
+        license_file: _,
         ref svd2pac_version,
-    } = settings;
+        keep_going: _,
+    } = *settings;
 
     info!("Start generating rust code");
-    // Read license file if specified
-    let custom_license_text = license_file
-        .as_ref()
-        .map(|path| {
-            fs::read_to_string(path)
-                .with_context(|| format!("Unable to read license file {path:?}"))
-        })
-        .transpose()?;
-
-    let xml = &mut String::new();
-    get_xml_string(xml_path, xml)?;
-    let svd_device = xml2ir::parse_xml(xml, svd_validation_level)?;
-    let ir = xml2ir::svd_device2ir(&svd_device, &custom_license_text)?;
+    let ir = read_ir(xml_path, settings)?;
     //Precompile templates
     let mut tera = get_tera_instance()?;
     precompile_tera(&mut tera);
@@ -542,6 +936,10 @@ pub(crate) fn generate_rust_package(
     context.insert("ir", &ir);
     context.insert("target", &target);
     context.insert("tracing", &tracing);
+    context.insert("bit_band", &bit_band);
+    context.insert("alias_registers", &alias_registers);
+    context.insert("defmt", &defmt);
+    context.insert("serde", &serde);
     context.insert("package_name", &package_name);
     context.insert("description", "Description tests");
     context.insert("svd2pac_version", svd2pac_version);
@@ -553,34 +951,39 @@ pub(crate) fn generate_rust_package(
         &ir,
         "peri_mod.tera",
         destination_folder,
-        svd2pac_version,
         &now,
+        settings,
     )?;
 
     //Generate common module
     generate_common_module(&tera, &ir, destination_folder, &context)?;
 
-    // Generate tracing related modules
-    if tracing {
-        generate_tracing_module(&tera, &ir, destination_folder, &context)?;
-    }
+    //Generate metadata module
+    generate_metadata_module(&tera, destination_folder, &context)?;
 
     // If target is aurix, create csfr modules
-    if settings.target == Target::Aurix {
-        let ir_csfr = generate_aurix_core_ir(xml_path, &settings)?;
-
-        // Generate cpu peripheral modules
-        if let Some(ref ir) = ir_csfr {
-            generate_peripheral_module(
-                &tera,
-                ir,
-                "aurix_core.tera",
-                destination_folder,
-                svd2pac_version,
-                &now,
-            )?;
-            context.insert("ir_csfr", &ir_csfr);
-        }
+    let ir_csfr = if settings.target == Target::Aurix {
+        generate_aurix_core_ir(xml_path, settings)?
+    } else {
+        None
+    };
+
+    // Generate cpu peripheral modules
+    if let Some(ref ir) = ir_csfr {
+        generate_peripheral_module(
+            &tera,
+            ir,
+            "aurix_core.tera",
+            destination_folder,
+            &now,
+            settings,
+        )?;
+        context.insert("ir_csfr", &ir_csfr);
+    }
+
+    // Generate tracing related modules
+    if tracing {
+        generate_tracing_module(&tera, &ir, ir_csfr.as_ref(), destination_folder, &context)?;
     }
 
     //generate lib.rs
@@ -631,3 +1034,148 @@ pub(crate) fn generate_rust_package(
     info!("Completed code generation");
     Ok(())
 }
+
+/// Generate CMSIS-style C header `<device name>.h` in `destination_folder`
+pub(crate) fn generate_c_header(
+    xml_path: &Path,
+    destination_folder: &Path,
+    settings: &GenPkgSettings,
+) -> anyhow::Result<()> {
+    info!("Start generating C header");
+    let ir = read_ir(xml_path, settings)?;
+    let mut tera = get_tera_instance()?;
+    precompile_tera(&mut tera);
+
+    let header = c_header::CHeader::new(&ir, settings.target);
+    let header_guard = format!("{}_H", ir.device.name.remove_invalid_char().to_uppercase());
+    let mut context = tera::Context::new();
+    context.insert("ir", &ir);
+    context.insert("header", &header);
+    context.insert("header_guard", &header_guard);
+    context.insert("svd2pac_version", &settings.svd2pac_version);
+    context.insert("now", &chrono::Utc::now().to_rfc2822());
+    execute_template(
+        &tera,
+        "c_header.tera",
+        &context,
+        &destination_folder.join(format!("{}.h", ir.device.name)),
+    )
+    .context("Failed to generate C header")?;
+    info!("Completed C header generation");
+    Ok(())
+}
+
+/// Generate HTML register manual in folder `docs` of `destination_folder`
+pub(crate) fn generate_docs(
+    xml_path: &Path,
+    destination_folder: &Path,
+    settings: &GenPkgSettings,
+) -> anyhow::Result<()> {
+    info!("Start generating register manual");
+    let ir = read_ir(xml_path, settings)?;
+    let mut tera = get_tera_instance()?;
+    precompile_tera(&mut tera);
+
+    let docs_folder = destination_folder.join("docs");
+    let manual = docs::DocManual::new(&ir);
+    let mut context = tera::Context::new();
+    context.insert("ir", &ir);
+    context.insert("manual", &manual);
+    context.insert("svd2pac_version", &settings.svd2pac_version);
+    context.insert("now", &chrono::Utc::now().to_rfc2822());
+    execute_template(
+        &tera,
+        "docs_index.tera",
+        &context,
+        &docs_folder.join("index.html"),
+    )
+    .context("Failed to generate index of register manual")?;
+    for peripheral in &manual.peripherals {
+        context.insert("peripheral", peripheral);
+        execute_template(
+            &tera,
+            "docs_peripheral.tera",
+            &context,
+            &docs_folder.join(&peripheral.page),
+        )
+        .with_context(|| format!("Failed to generate manual of {}", peripheral.name))?;
+    }
+    info!("Completed register manual generation");
+    Ok(())
+}
+
+/// Write the SVD file after expansion of arrays, resolution of `derivedFrom` and propagation
+/// of register properties to `output_path`
+pub(crate) fn generate_normalized_svd(
+    xml_path: &Path,
+    output_path: &Path,
+    settings: &GenPkgSettings,
+) -> anyhow::Result<()> {
+    info!("Start generating normalized SVD");
+    let xml = &mut String::new();
+    get_xml_string(xml_path, xml)?;
+    let svd_device = xml2ir::parse_xml(xml, settings.svd_validation_level)
+        .map_err(|err| xml2ir::add_source_location(err, xml_path, xml))?;
+    let svd_device = svd_util::normalize_device(&svd_device)?;
+    let svd_xml = svd_util::encode_device(&svd_device)?;
+    if let Some(folder) = output_path.parent() {
+        create_dir_all(folder)?;
+    }
+    fs::write(output_path, svd_xml).context(format!("Error while writing {output_path:?}"))?;
+    info!("Completed normalized SVD generation");
+    Ok(())
+}
+
+/// Compare the peripherals, registers and bitfields of two SVD files
+pub(crate) fn diff_svd(
+    old_xml_path: &Path,
+    new_xml_path: &Path,
+    svd_validation_level: SvdValidationLevel,
+) -> anyhow::Result<diff::SvdDiff> {
+    let parse_ir = |xml_path: &Path| -> anyhow::Result<ir::IR> {
+        let xml = &mut String::new();
+        get_xml_string(xml_path, xml)
+            .with_context(|| format!("Failed to read {}", xml_path.display()))?;
+        xml2ir::parse_xml(xml, svd_validation_level)
+            .and_then(|svd_device| xml2ir::svd_device2ir(&svd_device, &None, false))
+            .map_err(|err| xml2ir::add_source_location(err, xml_path, xml))
+    };
+    let old_ir = parse_ir(old_xml_path)?;
+    let new_ir = parse_ir(new_xml_path)?;
+    Ok(diff::SvdDiff::new(&old_ir, &new_ir))
+}
+
+/// Check an SVD file without generating code
+pub(crate) fn lint_svd(
+    xml_path: &Path,
+    svd_validation_level: SvdValidationLevel,
+) -> anyhow::Result<lint::LintReport> {
+    let xml = &mut String::new();
+    get_xml_string(xml_path, xml)
+        .with_context(|| format!("Failed to read {}", xml_path.display()))?;
+    let svd_device = xml2ir::parse_xml(xml, svd_validation_level)
+        .map_err(|err| xml2ir::add_source_location(err, xml_path, xml))?;
+    let expanded_device = svd_util::normalize_device(&svd_device)?;
+    let mut report = lint::LintReport::new(&svd_device, &expanded_device);
+    // Missing license is already reported
+    match xml2ir::svd_device2ir_keep_going(&svd_device, &Some(String::new()), false) {
+        Ok((_, errors)) => {
+            for err in errors {
+                report.push_expanded(
+                    &svd_device,
+                    lint::Severity::Error,
+                    "unsupported",
+                    &err.svd_path,
+                    format!("code generation fails: {}", err.kind),
+                );
+            }
+        }
+        Err(err) => report.push(
+            lint::Severity::Error,
+            "unsupported",
+            &svd_device.name,
+            format!("code generation fails: {err}"),
+        ),
+    }
+    Ok(report)
+}
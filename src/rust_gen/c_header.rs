@@ -0,0 +1,489 @@
+//! Model of a CMSIS-style C header built from [`IR`].
+//!
+//! Layout of the C structs (reserved padding and unions of overlapping registers)
+//! is computed here, the header itself is rendered by the templates in `templates/c`.
+use super::ir::*;
+use super::util::ToSanitizedSymbol;
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Member of a C struct describing a peripheral or a cluster
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "kind")]
+pub enum CMember {
+    Register {
+        name: String,
+        description: String,
+        offset: u64,
+        /// `__IM`, `__OM` or `__IOM`
+        qualifier: &'static str,
+        c_type: &'static str,
+        /// Length of the array. 1 if the member is not an array.
+        dim: u32,
+    },
+    Cluster {
+        name: String,
+        description: String,
+        offset: u64,
+        type_name: String,
+        /// Length of the array. 1 if the member is not an array.
+        dim: u32,
+    },
+    Reserved {
+        name: String,
+        offset: u64,
+        size: u64,
+    },
+    /// Members that share the same memory
+    Union { members: Vec<CMember> },
+    /// Used in union for members that start after the beginning of the union
+    Struct { members: Vec<CMember> },
+}
+
+/// Typedef struct of a peripheral or a cluster
+#[derive(Serialize, Debug)]
+pub struct CStruct {
+    pub type_name: String,
+    pub description: String,
+    pub members: Vec<CMember>,
+    pub size: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CEnumValue {
+    pub name: String,
+    pub description: String,
+    pub value: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CField {
+    pub name: String,
+    pub description: String,
+    pub pos: u32,
+    pub mask: u64,
+    /// Suffix of integer literals, `ULL` is used for 64 bit registers
+    pub literal_suffix: &'static str,
+    pub values: Vec<CEnumValue>,
+}
+
+/// `_Pos`, `_Msk` and enumerated value macros of the bitfields of a register
+#[derive(Serialize, Debug)]
+pub struct CRegisterDefs {
+    /// Prefix of all macros of the register: `<PERIPHERAL>_<CLUSTER>_<REGISTER>`
+    pub prefix: String,
+    pub fields: Vec<CField>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CPeripheral {
+    pub name: String,
+    pub description: String,
+    pub type_name: String,
+    pub base_addr: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CInterrupt {
+    pub name: String,
+    pub description: String,
+    pub value: i32,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct CHeader {
+    /// Structs in order of definition. Structs of clusters precede the struct that contains them.
+    pub structs: Vec<CStruct>,
+    pub registers: Vec<CRegisterDefs>,
+    pub peripherals: Vec<CPeripheral>,
+    pub interrupts: Vec<CInterrupt>,
+}
+
+/// Cortex-M system exceptions with negative `IRQn_Type` value
+const CORTEX_M_EXCEPTIONS: [(&str, &str, i32); 9] = [
+    ("NonMaskableInt", "Non maskable interrupt", -14),
+    ("HardFault", "Hard fault", -13),
+    ("MemoryManagement", "Memory management fault", -12),
+    ("BusFault", "Bus fault", -11),
+    ("UsageFault", "Usage fault", -10),
+    ("SVCall", "Supervisor call", -5),
+    ("DebugMonitor", "Debug monitor", -4),
+    ("PendSV", "Pendable request for system service", -2),
+    ("SysTick", "System tick timer", -1),
+];
+
+/// Member of a struct with its position, before layout
+struct Item {
+    offset: u64,
+    size: u64,
+    member: CMember,
+}
+
+trait ToCIdent {
+    fn to_c_ident(&self) -> String;
+}
+
+/// Keywords of C and C++ that can appear as names in SVD files
+const C_KEYWORDS: [&str; 20] = [
+    "auto", "break", "case", "char", "class", "const", "default", "delete", "do", "double", "else",
+    "enum", "for", "if", "int", "new", "register", "return", "struct", "union",
+];
+
+impl ToCIdent for str {
+    fn to_c_ident(&self) -> String {
+        let ident = self.to_internal_ident().remove_invalid_char();
+        if ident.starts_with(|c: char| c.is_ascii_digit()) {
+            "_".to_owned() + &ident
+        } else if C_KEYWORDS.contains(&ident.as_str()) {
+            ident + "_"
+        } else {
+            ident
+        }
+    }
+}
+
+/// Description as single line that can be placed in a C comment
+fn to_c_comment(description: &str) -> String {
+    description
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("*/", "* /")
+}
+
+fn c_type(size: &BitSize) -> &'static str {
+    match size {
+        BitSize::BIT8 => "uint8_t",
+        BitSize::BIT16 => "uint16_t",
+        BitSize::BIT32 => "uint32_t",
+        BitSize::BIT64 => "uint64_t",
+    }
+}
+
+/// Name of element `index` of an array
+fn element_name(name: &str, dim_index: &[String], index: u32) -> String {
+    dim_index
+        .get(index as usize)
+        .map_or_else(|| format!("{name}{index}"), |name| name.clone())
+        .to_c_ident()
+}
+
+impl CHeader {
+    pub fn new(ir: &IR, target: crate::Target) -> Self {
+        let mut header = CHeader::default();
+        // Peripherals that share the same module share the same struct and bitfield macros
+        let mut type_names: HashMap<String, String> = HashMap::new();
+        for (name, peripheral) in &ir.device.peripheral_mod {
+            let peripheral = peripheral.borrow();
+            let type_name = match type_names.get(&peripheral.module_id) {
+                Some(type_name) => type_name.clone(),
+                None => {
+                    let (type_name, _) = header.add_struct(
+                        &name.to_c_ident(),
+                        &peripheral.description,
+                        &peripheral.registers,
+                        &peripheral.clusters,
+                        0,
+                    );
+                    type_names.insert(peripheral.module_id.clone(), type_name.clone());
+                    type_name
+                }
+            };
+            for (index, base_addr) in peripheral.base_addr.iter().enumerate() {
+                let name = if peripheral.base_addr.len() == 1 {
+                    name.to_c_ident()
+                } else {
+                    format!("{}{index}", name.to_c_ident())
+                };
+                header.peripherals.push(CPeripheral {
+                    name,
+                    description: to_c_comment(&peripheral.description),
+                    type_name: type_name.clone(),
+                    base_addr: *base_addr,
+                });
+            }
+        }
+        if target == crate::Target::CortexM {
+            header.interrupts.extend(CORTEX_M_EXCEPTIONS.iter().map(
+                |(name, description, value)| CInterrupt {
+                    name: (*name).to_owned(),
+                    description: (*description).to_owned(),
+                    value: *value,
+                },
+            ));
+        }
+        header.interrupts.extend(
+            ir.interrupt_table
+                .iter()
+                .flatten()
+                .map(|interrupt| CInterrupt {
+                    name: interrupt.name.to_c_ident(),
+                    description: to_c_comment(&interrupt.description),
+                    value: interrupt.value as i32,
+                }),
+        );
+        // Members named like a peripheral would be replaced by the macro of the peripheral
+        let peripheral_names: HashSet<&str> = header
+            .peripherals
+            .iter()
+            .map(|peripheral| peripheral.name.as_str())
+            .collect();
+        for c_struct in &mut header.structs {
+            rename_members(&mut c_struct.members, &peripheral_names);
+        }
+        header
+    }
+
+    /// Add the struct describing `registers` and `clusters` and return its type name and its size.
+    /// The struct is padded to `min_size`.
+    fn add_struct(
+        &mut self,
+        prefix: &str,
+        description: &str,
+        registers: &IndexMap<String, Rc<RefCell<Register>>>,
+        clusters: &IndexMap<String, Rc<RefCell<Cluster>>>,
+        min_size: u64,
+    ) -> (String, u64) {
+        let mut items = Vec::new();
+        for register in registers.values() {
+            let register = register.borrow();
+            self.add_register_defs(prefix, &register);
+            items.extend(register_items(&register));
+        }
+        for cluster in clusters.values() {
+            let cluster = cluster.borrow();
+            let min_size = if cluster.dim > 1 {
+                cluster.dim_increment as u64
+            } else {
+                0
+            };
+            let (type_name, size) = self.add_struct(
+                &format!("{prefix}_{}", cluster.name.to_c_ident()),
+                &cluster.description,
+                &cluster.registers,
+                &cluster.clusters,
+                min_size,
+            );
+            items.extend(cluster_items(&cluster, type_name, size));
+        }
+        let mut reserved_count = 0;
+        let (mut members, size) = layout(items, &mut reserved_count);
+        let size = if size < min_size {
+            members.push(reserved(&mut reserved_count, size, min_size - size));
+            min_size
+        } else {
+            size
+        };
+        let type_name = format!("{prefix}_Type");
+        self.structs.push(CStruct {
+            type_name: type_name.clone(),
+            description: to_c_comment(description),
+            members,
+            size,
+        });
+        (type_name, size)
+    }
+
+    fn add_register_defs(&mut self, prefix: &str, register: &Register) {
+        let literal_suffix = if register.size == BitSize::BIT64 {
+            "ULL"
+        } else {
+            "UL"
+        };
+        let mut fields = Vec::new();
+        for field in register.resolved_fields().values() {
+            let field = field.borrow();
+            let enum_type_name = field
+                .enum_type_read
+                .as_ref()
+                .or(field.enum_type_write.as_ref());
+            let enum_type = field
+                .enum_types
+                .iter()
+                .find(|enum_type| Some(&enum_type.name) == enum_type_name);
+            for index in 0..field.dim {
+                let name = if field.dim == 1 {
+                    field.name.to_c_ident()
+                } else {
+                    element_name(&field.name, &field.dim_index, index)
+                };
+                let values = enum_type
+                    .map(|enum_type| {
+                        enum_type
+                            .values
+                            .iter()
+                            .map(|value| CEnumValue {
+                                name: value.name.to_c_ident(),
+                                description: to_c_comment(&value.description),
+                                value: value.value,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                fields.push(CField {
+                    name,
+                    description: to_c_comment(&field.description),
+                    pos: field.offset + index * field.dim_increment,
+                    mask: field.mask.into(),
+                    literal_suffix,
+                    values,
+                });
+            }
+        }
+        self.registers.push(CRegisterDefs {
+            prefix: format!("{prefix}_{}", register.name.to_c_ident()),
+            fields,
+        });
+    }
+}
+
+fn register_items(register: &Register) -> Vec<Item> {
+    let size = register.size.bytes() as u64;
+    let qualifier = match register.access {
+        RegisterAccess::R => "__IM",
+        RegisterAccess::W => "__OM",
+        RegisterAccess::RW => "__IOM",
+    };
+    let item = |name: String, offset: u64, dim: u32| Item {
+        offset,
+        size: size * dim as u64,
+        member: CMember::Register {
+            name,
+            description: to_c_comment(&register.description),
+            offset,
+            qualifier,
+            c_type: c_type(&register.size),
+            dim,
+        },
+    };
+    let name = register.name.to_c_ident();
+    if register.dim == 1 {
+        vec![item(name, register.offset as u64, 1)]
+    } else if register.dim_increment as u64 == size {
+        // Array names like `REG_[%s]` end with `_` once placeholder is removed
+        let name = name.trim_end_matches('_').to_owned();
+        vec![item(name, register.offset as u64, register.dim)]
+    } else {
+        // Registers that are not contiguous cannot be described as C array
+        (0..register.dim)
+            .map(|index| {
+                item(
+                    element_name(&register.name, &register.dim_index, index),
+                    (register.offset + index * register.dim_increment) as u64,
+                    1,
+                )
+            })
+            .collect()
+    }
+}
+
+fn cluster_items(cluster: &Cluster, type_name: String, size: u64) -> Vec<Item> {
+    let item = |name: String, offset: u64, dim: u32| Item {
+        offset,
+        size: size * dim as u64,
+        member: CMember::Cluster {
+            name,
+            description: to_c_comment(&cluster.description),
+            offset,
+            type_name: type_name.clone(),
+            dim,
+        },
+    };
+    let name = cluster.name.to_c_ident();
+    if cluster.dim == 1 {
+        vec![item(name, cluster.offset as u64, 1)]
+    } else if cluster.dim_increment as u64 == size {
+        // Array names like `REG_[%s]` end with `_` once placeholder is removed
+        let name = name.trim_end_matches('_').to_owned();
+        vec![item(name, cluster.offset as u64, cluster.dim)]
+    } else {
+        // Overlapping clusters cannot be described as C array
+        (0..cluster.dim)
+            .map(|index| {
+                item(
+                    element_name(&cluster.name, &cluster.dim_index, index),
+                    (cluster.offset + index * cluster.dim_increment) as u64,
+                    1,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Append `_` to names of members that are in `names`
+fn rename_members(members: &mut [CMember], names: &HashSet<&str>) {
+    for member in members {
+        match member {
+            CMember::Register { name, .. } | CMember::Cluster { name, .. } => {
+                if names.contains(name.as_str()) {
+                    name.push('_');
+                }
+            }
+            CMember::Union { members } | CMember::Struct { members } => {
+                rename_members(members, names)
+            }
+            CMember::Reserved { .. } => {}
+        }
+    }
+}
+
+fn reserved(reserved_count: &mut u32, offset: u64, size: u64) -> CMember {
+    let name = if *reserved_count == 0 {
+        "RESERVED".to_owned()
+    } else {
+        format!("RESERVED{reserved_count}")
+    };
+    *reserved_count += 1;
+    CMember::Reserved { name, offset, size }
+}
+
+/// Sort the items by offset, insert reserved padding in the gaps and group overlapping items in unions.
+/// Return the members and the size of the struct.
+fn layout(mut items: Vec<Item>, reserved_count: &mut u32) -> (Vec<CMember>, u64) {
+    items.sort_by_key(|item| item.offset);
+    let mut groups: Vec<(u64, u64, Vec<Item>)> = Vec::new();
+    for item in items {
+        let item_end = item.offset + item.size;
+        match groups.last_mut() {
+            Some((_, group_end, group)) if item.offset < *group_end => {
+                *group_end = (*group_end).max(item_end);
+                group.push(item);
+            }
+            _ => groups.push((item.offset, item_end, vec![item])),
+        }
+    }
+    let mut members = Vec::new();
+    let mut cursor = 0;
+    for (group_start, group_end, mut group) in groups {
+        if group_start > cursor {
+            members.push(reserved(reserved_count, cursor, group_start - cursor));
+        }
+        if group.len() == 1 {
+            members.push(group.pop().unwrap().member);
+        } else {
+            let union_members = group
+                .into_iter()
+                .map(|item| {
+                    if item.offset == group_start {
+                        item.member
+                    } else {
+                        CMember::Struct {
+                            members: vec![
+                                reserved(reserved_count, group_start, item.offset - group_start),
+                                item.member,
+                            ],
+                        }
+                    }
+                })
+                .collect();
+            members.push(CMember::Union {
+                members: union_members,
+            });
+        }
+        cursor = group_end;
+    }
+    (members, cursor)
+}
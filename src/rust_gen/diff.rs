@@ -0,0 +1,591 @@
+//! Semantic comparison of the [`IR`] of two SVD files.
+//!
+//! Peripherals, clusters, registers, bitfields, enumerated values and interrupts are matched by name.
+//! Clusters, registers and bitfields are identified by their path, e.g. `TIMER.Cluster1.CR.PSC`.
+use super::ir::*;
+use super::util::ToSanitizedSymbol;
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Peripheral,
+    Cluster,
+    Register,
+    Field,
+    EnumValue,
+    Interrupt,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// Address of peripheral, offset of register or bit offset of bitfield changed
+    Moved,
+    Modified,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub item: ItemKind,
+    /// Path of the item, e.g. `PERIPHERAL.CLUSTER.REGISTER.FIELD`
+    pub path: String,
+    /// Changed property. None for added and removed items.
+    pub property: Option<&'static str>,
+    pub old: Option<String>,
+    pub new: Option<String>,
+    /// Code using the PAC generated from the old SVD may not compile with the PAC generated from the new SVD
+    pub breaking: bool,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (marker, verb) = match self.kind {
+            ChangeKind::Added => ('+', "added"),
+            ChangeKind::Removed => ('-', "removed"),
+            ChangeKind::Moved => ('>', "moved"),
+            ChangeKind::Modified => ('~', "modified"),
+        };
+        let item = match self.item {
+            ItemKind::Peripheral => "peripheral",
+            ItemKind::Cluster => "cluster",
+            ItemKind::Register => "register",
+            ItemKind::Field => "field",
+            ItemKind::EnumValue => "enum value",
+            ItemKind::Interrupt => "interrupt",
+        };
+        write!(f, "{marker} {verb} {item} {}", self.path)?;
+        if let Some(property) = self.property {
+            write!(
+                f,
+                ": {property} {} -> {}",
+                self.old.as_deref().unwrap_or("-"),
+                self.new.as_deref().unwrap_or("-")
+            )?;
+        }
+        if self.breaking {
+            write!(f, " [breaking]")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct SvdDiff {
+    pub changes: Vec<Change>,
+    /// At least one change is breaking
+    pub breaking: bool,
+}
+
+impl fmt::Display for SvdDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        let breaking = self.changes.iter().filter(|change| change.breaking).count();
+        write!(f, "{} changes, {breaking} breaking", self.changes.len())
+    }
+}
+
+struct PeripheralItem {
+    base_addr: Vec<u64>,
+}
+
+struct ClusterItem {
+    offset: u64,
+    dim: Dim,
+}
+
+struct RegisterItem {
+    offset: u64,
+    size: BitSize,
+    access: RegisterAccess,
+    reset_value: u64,
+    dim: Dim,
+}
+
+struct FieldItem {
+    offset: u32,
+    width: u32,
+    /// Size of the Rust type of the bitfield
+    size: BitSize,
+    access: RegisterBitfieldAccess,
+    dim: Dim,
+    /// Enumerated values of read and write enums. None if bitfield is not enumerated.
+    enum_values: Option<IndexMap<String, u64>>,
+}
+
+/// Number of elements and names of the indexes of an array. Dimension of single items is 1.
+#[derive(PartialEq)]
+struct Dim {
+    dim: u32,
+    dim_index: Vec<String>,
+}
+
+impl Dim {
+    fn new(dim: u32, dim_index: &[String]) -> Self {
+        Dim {
+            dim,
+            dim_index: dim_index.to_vec(),
+        }
+    }
+
+    /// Accessors of single items and arrays differ. Removed indexes or renamed accessors of indexes break code.
+    fn is_reduced(&self, new: &Dim) -> bool {
+        (self.dim > 1) != (new.dim > 1)
+            || new.dim < self.dim
+            || self
+                .dim_index
+                .iter()
+                .any(|index| !new.dim_index.contains(index))
+    }
+}
+
+/// Peripherals, clusters, registers, bitfields and interrupts of a device indexed by path
+#[derive(Default)]
+struct Items {
+    peripherals: IndexMap<String, PeripheralItem>,
+    clusters: IndexMap<String, ClusterItem>,
+    registers: IndexMap<String, RegisterItem>,
+    fields: IndexMap<String, FieldItem>,
+    /// Values of interrupts by name of the variant of `Interrupt` enum
+    interrupts: IndexMap<String, u32>,
+}
+
+impl Items {
+    fn new(ir: &IR) -> Self {
+        let mut items = Items::default();
+        for (name, peripheral) in &ir.device.peripheral_mod {
+            let peripheral = peripheral.borrow();
+            items.peripherals.insert(
+                name.clone(),
+                PeripheralItem {
+                    base_addr: peripheral.base_addr.clone(),
+                },
+            );
+            items.add_registers(name, 0, &peripheral.registers, &peripheral.clusters);
+        }
+        for interrupt in ir.interrupt_table.iter().flatten() {
+            items
+                .interrupts
+                .insert(interrupt.name.to_uppercase(), interrupt.value);
+        }
+        items
+    }
+
+    fn add_registers(
+        &mut self,
+        path: &str,
+        offset: u64,
+        registers: &IndexMap<String, Rc<RefCell<Register>>>,
+        clusters: &IndexMap<String, Rc<RefCell<Cluster>>>,
+    ) {
+        for register in registers.values() {
+            let register = register.borrow();
+            let register_path = format!("{path}.{}", register.name.to_internal_ident());
+            // Derived registers are compared with the bitfields of their base register
+            for field in register.resolved_fields().values() {
+                let field = field.borrow();
+                let enum_values = (!field.enum_types.is_empty()).then(|| {
+                    field
+                        .enum_types
+                        .iter()
+                        .flat_map(|enum_type| &enum_type.values)
+                        .map(|value| (value.name.clone(), value.value))
+                        .collect()
+                });
+                self.fields.insert(
+                    format!("{register_path}.{}", field.name.to_internal_ident()),
+                    FieldItem {
+                        offset: field.offset,
+                        width: u32::BITS - field.mask.leading_zeros(),
+                        size: field.size.clone(),
+                        access: field.access.clone(),
+                        dim: Dim::new(field.dim, &field.dim_index),
+                        enum_values,
+                    },
+                );
+            }
+            self.registers.insert(
+                register_path,
+                RegisterItem {
+                    offset: offset + register.offset as u64,
+                    size: register.size.clone(),
+                    access: register.access.clone(),
+                    reset_value: register.reset_value,
+                    dim: Dim::new(register.dim, &register.dim_index),
+                },
+            );
+        }
+        for cluster in clusters.values() {
+            let cluster = cluster.borrow();
+            let cluster_path = format!("{path}.{}", cluster.name.to_internal_ident());
+            let cluster_offset = offset + cluster.offset as u64;
+            self.add_registers(
+                &cluster_path,
+                cluster_offset,
+                &cluster.registers,
+                &cluster.clusters,
+            );
+            self.clusters.insert(
+                cluster_path,
+                ClusterItem {
+                    offset: cluster_offset,
+                    dim: Dim::new(cluster.dim, &cluster.dim_index),
+                },
+            );
+        }
+    }
+}
+
+/// Readable and writable flags of an access
+fn register_access(access: &RegisterAccess) -> (bool, bool) {
+    match access {
+        RegisterAccess::R => (true, false),
+        RegisterAccess::W => (false, true),
+        RegisterAccess::RW => (true, true),
+    }
+}
+
+fn field_access(access: &RegisterBitfieldAccess) -> (bool, bool) {
+    match access {
+        RegisterBitfieldAccess::R => (true, false),
+        RegisterBitfieldAccess::W => (false, true),
+        RegisterBitfieldAccess::RW => (true, true),
+    }
+}
+
+fn access_name((read, write): (bool, bool)) -> String {
+    match (read, write) {
+        (true, false) => "read-only",
+        (false, true) => "write-only",
+        _ => "read-write",
+    }
+    .to_owned()
+}
+
+/// Removing read or write access removes accessors from the PAC
+fn is_access_reduced(old: (bool, bool), new: (bool, bool)) -> bool {
+    (old.0 && !new.0) || (old.1 && !new.1)
+}
+
+/// Report changes of number of elements and names of indexes of an array with `modified`
+fn compare_dim(
+    modified: &mut impl FnMut(ChangeKind, &'static str, String, String, bool),
+    old: &Dim,
+    new: &Dim,
+) {
+    let breaking = old.is_reduced(new);
+    if old.dim != new.dim {
+        modified(
+            ChangeKind::Modified,
+            "dim",
+            old.dim.to_string(),
+            new.dim.to_string(),
+            breaking,
+        );
+    }
+    if old.dim_index != new.dim_index {
+        modified(
+            ChangeKind::Modified,
+            "dim_index",
+            old.dim_index.join(","),
+            new.dim_index.join(","),
+            breaking,
+        );
+    }
+}
+
+impl SvdDiff {
+    pub fn new(old: &IR, new: &IR) -> Self {
+        let old = Items::new(old);
+        let new = Items::new(new);
+        let mut diff = SvdDiff::default();
+        diff.compare(
+            ItemKind::Peripheral,
+            &old.peripherals,
+            &new.peripherals,
+            |diff, path, old, new| {
+                if old.base_addr != new.base_addr {
+                    let format = |base_addr: &[u64]| {
+                        base_addr
+                            .iter()
+                            .map(|addr| format!("{addr:#x}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    };
+                    // Elements of peripheral arrays are accessed by index
+                    let breaking = (old.base_addr.len() > 1) != (new.base_addr.len() > 1);
+                    diff.push_modified(
+                        ChangeKind::Moved,
+                        ItemKind::Peripheral,
+                        path,
+                        "address",
+                        format(&old.base_addr),
+                        format(&new.base_addr),
+                        breaking,
+                    );
+                }
+            },
+        );
+        diff.compare(
+            ItemKind::Cluster,
+            &old.clusters,
+            &new.clusters,
+            |diff, path, old, new| {
+                let mut modified = |kind, property, old: String, new: String, breaking| {
+                    diff.push_modified(kind, ItemKind::Cluster, path, property, old, new, breaking)
+                };
+                if old.offset != new.offset {
+                    modified(
+                        ChangeKind::Moved,
+                        "offset",
+                        format!("{:#x}", old.offset),
+                        format!("{:#x}", new.offset),
+                        false,
+                    );
+                }
+                compare_dim(&mut modified, &old.dim, &new.dim);
+            },
+        );
+        diff.compare(
+            ItemKind::Register,
+            &old.registers,
+            &new.registers,
+            |diff, path, old, new| {
+                let mut modified = |kind, property, old: String, new: String, breaking| {
+                    diff.push_modified(kind, ItemKind::Register, path, property, old, new, breaking)
+                };
+                if old.offset != new.offset {
+                    modified(
+                        ChangeKind::Moved,
+                        "offset",
+                        format!("{:#x}", old.offset),
+                        format!("{:#x}", new.offset),
+                        false,
+                    );
+                }
+                if old.size != new.size {
+                    modified(
+                        ChangeKind::Modified,
+                        "width",
+                        old.size.bits().to_string(),
+                        new.size.bits().to_string(),
+                        true,
+                    );
+                }
+                let (old_access, new_access) =
+                    (register_access(&old.access), register_access(&new.access));
+                if old_access != new_access {
+                    modified(
+                        ChangeKind::Modified,
+                        "access",
+                        access_name(old_access),
+                        access_name(new_access),
+                        is_access_reduced(old_access, new_access),
+                    );
+                }
+                if old.reset_value != new.reset_value {
+                    modified(
+                        ChangeKind::Modified,
+                        "reset_value",
+                        format!("{:#x}", old.reset_value),
+                        format!("{:#x}", new.reset_value),
+                        false,
+                    );
+                }
+                compare_dim(&mut modified, &old.dim, &new.dim);
+            },
+        );
+        diff.compare(
+            ItemKind::Field,
+            &old.fields,
+            &new.fields,
+            |diff, path, old, new| {
+                let mut modified = |kind, property, old: String, new: String, breaking| {
+                    diff.push_modified(kind, ItemKind::Field, path, property, old, new, breaking)
+                };
+                if old.offset != new.offset {
+                    modified(
+                        ChangeKind::Moved,
+                        "bit_offset",
+                        old.offset.to_string(),
+                        new.offset.to_string(),
+                        false,
+                    );
+                }
+                if old.width != new.width {
+                    // Type of raw value of bitfield depends on width
+                    modified(
+                        ChangeKind::Modified,
+                        "width",
+                        old.width.to_string(),
+                        new.width.to_string(),
+                        old.size != new.size,
+                    );
+                }
+                let (old_access, new_access) =
+                    (field_access(&old.access), field_access(&new.access));
+                if old_access != new_access {
+                    modified(
+                        ChangeKind::Modified,
+                        "access",
+                        access_name(old_access),
+                        access_name(new_access),
+                        is_access_reduced(old_access, new_access),
+                    );
+                }
+                compare_dim(&mut modified, &old.dim, &new.dim);
+                match (&old.enum_values, &new.enum_values) {
+                    (Some(old_values), Some(new_values)) => {
+                        diff.compare_enum_values(path, old_values, new_values)
+                    }
+                    (None, None) => {}
+                    // Bitfield changes between raw value and enumerated type
+                    (old_values, new_values) => diff.push_modified(
+                        ChangeKind::Modified,
+                        ItemKind::Field,
+                        path,
+                        "enumerated",
+                        old_values.is_some().to_string(),
+                        new_values.is_some().to_string(),
+                        true,
+                    ),
+                }
+            },
+        );
+        // Interrupts are variants of `Interrupt` enum
+        diff.compare(
+            ItemKind::Interrupt,
+            &old.interrupts,
+            &new.interrupts,
+            |diff, path, old, new| {
+                if old != new {
+                    diff.push_modified(
+                        ChangeKind::Modified,
+                        ItemKind::Interrupt,
+                        path,
+                        "value",
+                        old.to_string(),
+                        new.to_string(),
+                        false,
+                    );
+                }
+            },
+        );
+        diff.remove_nested_changes();
+        diff.breaking = diff.changes.iter().any(|change| change.breaking);
+        diff
+    }
+
+    /// Items of added or removed peripherals, clusters and registers are not reported
+    fn remove_nested_changes(&mut self) {
+        let added_removed: HashSet<(ChangeKind, String)> = self
+            .changes
+            .iter()
+            .filter(|change| matches!(change.kind, ChangeKind::Added | ChangeKind::Removed))
+            .map(|change| (change.kind, change.path.clone()))
+            .collect();
+        self.changes.retain(|change| {
+            let mut path = change.path.as_str();
+            while let Some((parent, _)) = path.rsplit_once('.') {
+                if added_removed.contains(&(change.kind, parent.to_owned())) {
+                    return false;
+                }
+                path = parent;
+            }
+            true
+        });
+    }
+
+    /// Report added and removed items and compare items present in both maps with `compare_item`
+    fn compare<T>(
+        &mut self,
+        item: ItemKind,
+        old: &IndexMap<String, T>,
+        new: &IndexMap<String, T>,
+        mut compare_item: impl FnMut(&mut Self, &str, &T, &T),
+    ) {
+        for (path, old_item) in old {
+            match new.get(path) {
+                Some(new_item) => compare_item(self, path, old_item, new_item),
+                None => self.push(ChangeKind::Removed, item, path, true),
+            }
+        }
+        for path in new.keys().filter(|path| !old.contains_key(*path)) {
+            self.push(ChangeKind::Added, item, path, false);
+        }
+    }
+
+    fn compare_enum_values(
+        &mut self,
+        field_path: &str,
+        old: &IndexMap<String, u64>,
+        new: &IndexMap<String, u64>,
+    ) {
+        self.compare(
+            ItemKind::EnumValue,
+            &old.iter()
+                .map(|(name, value)| (format!("{field_path}.{name}"), *value))
+                .collect(),
+            &new.iter()
+                .map(|(name, value)| (format!("{field_path}.{name}"), *value))
+                .collect(),
+            |diff, path, old, new| {
+                if old != new {
+                    diff.push_modified(
+                        ChangeKind::Modified,
+                        ItemKind::EnumValue,
+                        path,
+                        "value",
+                        format!("{old:#x}"),
+                        format!("{new:#x}"),
+                        false,
+                    );
+                }
+            },
+        );
+    }
+
+    fn push(&mut self, kind: ChangeKind, item: ItemKind, path: &str, breaking: bool) {
+        self.changes.push(Change {
+            kind,
+            item,
+            path: path.to_owned(),
+            property: None,
+            old: None,
+            new: None,
+            breaking,
+        });
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push_modified(
+        &mut self,
+        kind: ChangeKind,
+        item: ItemKind,
+        path: &str,
+        property: &'static str,
+        old: String,
+        new: String,
+        breaking: bool,
+    ) {
+        self.changes.push(Change {
+            kind,
+            item,
+            path: path.to_owned(),
+            property: Some(property),
+            old: Some(old),
+            new: Some(new),
+            breaking,
+        });
+    }
+}
@@ -0,0 +1,402 @@
+//! Model of the HTML register manual built from [`IR`].
+//!
+//! Register paths, bit diagrams and formatted values are computed here,
+//! the pages are rendered by the templates in `templates/docs`.
+use super::ir::*;
+use super::util::ToSanitizedSymbol;
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Number of bits shown in a row of the bit diagram of a register
+const BITS_PER_ROW: u32 = 16;
+
+/// Names of the index variables of nested arrays
+const INDEX_NAMES: [&str; 4] = ["i", "j", "k", "l"];
+
+#[derive(Serialize, Debug)]
+pub struct DocLink {
+    pub name: String,
+    pub page: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DocEnumValue {
+    pub name: String,
+    pub value: u64,
+    pub description: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DocField {
+    pub name: String,
+    /// Bit range as `[msb:lsb]` or `[bit]`
+    pub bits: String,
+    pub access: &'static str,
+    pub reset_value: String,
+    pub description: String,
+    pub values: Vec<DocEnumValue>,
+}
+
+/// Cell of the bit diagram. Bits not covered by a bitfield are reserved.
+#[derive(Serialize, Debug)]
+pub struct DocBitCell {
+    pub name: String,
+    pub width: u32,
+    pub reserved: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DocBitRow {
+    /// Bit numbers from most significant to least significant
+    pub bits: Vec<u32>,
+    pub cells: Vec<DocBitCell>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DocRegister {
+    /// Path of register. Registers in clusters are prefixed by the cluster names separated by `.`
+    pub name: String,
+    /// Id of the HTML element describing the register
+    pub anchor: String,
+    /// Offset from base address of peripheral
+    pub offset: u64,
+    /// Absolute address of the first instance of the peripheral
+    pub address: u64,
+    /// Description of the indexes of arrays in `name`. Empty if register is not in an array.
+    pub arrays: Vec<String>,
+    pub size: u32,
+    pub access: &'static str,
+    pub reset_value: String,
+    pub description: String,
+    pub fields: Vec<DocField>,
+    pub bit_rows: Vec<DocBitRow>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DocInterrupt {
+    pub name: String,
+    pub value: u32,
+    pub description: String,
+    /// Page of the peripheral that defines the interrupt
+    pub peripheral: Option<DocLink>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DocPeripheral {
+    pub name: String,
+    pub description: String,
+    /// File name of the page of the peripheral
+    pub page: String,
+    /// Base addresses. More than one for arrays of peripherals.
+    pub base_addr: Vec<u64>,
+    pub derived_from: Option<DocLink>,
+    /// Peripherals derived from this peripheral
+    pub derived_by: Vec<DocLink>,
+    pub registers: Vec<DocRegister>,
+    pub interrupts: Vec<DocInterrupt>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DocManual {
+    pub name: String,
+    pub description: String,
+    pub peripherals: Vec<DocPeripheral>,
+    pub interrupts: Vec<DocInterrupt>,
+}
+
+/// Position of a register or a cluster in the peripheral
+#[derive(Clone, Default)]
+struct DocPath {
+    name: String,
+    offset: u64,
+    arrays: Vec<String>,
+}
+
+impl DocPath {
+    /// Path of a child register or cluster
+    fn child(
+        &self,
+        name: &str,
+        offset: u32,
+        dim: u32,
+        dim_increment: u32,
+        dim_index: &[String],
+    ) -> Self {
+        let mut path = self.clone();
+        if !path.name.is_empty() {
+            path.name.push('.');
+        }
+        path.offset += offset as u64;
+        let name = name.to_internal_ident();
+        if dim > 1 {
+            let index_name = INDEX_NAMES.get(path.arrays.len()).copied().unwrap_or("n");
+            // Array names like `REG_[%s]` end with `_` once placeholder is removed
+            path.name += &format!("{}[{index_name}]", name.trim_end_matches('_'));
+            let indexes = if dim_index.is_empty() {
+                format!("0..{}", dim - 1)
+            } else {
+                format!("{{{}}}", dim_index.join(", "))
+            };
+            path.arrays
+                .push(format!("{index_name} = {indexes}, step {dim_increment:#x}"));
+        } else {
+            path.name += &name;
+        }
+        path
+    }
+}
+
+fn page_name(peripheral_name: &str) -> String {
+    format!(
+        "{}.html",
+        peripheral_name.remove_invalid_char().to_lowercase()
+    )
+}
+
+fn anchor(name: &str) -> String {
+    format!("reg-{}", name.remove_invalid_char())
+}
+
+fn register_access(access: &RegisterAccess) -> &'static str {
+    match access {
+        RegisterAccess::R => "read-only",
+        RegisterAccess::W => "write-only",
+        RegisterAccess::RW => "read-write",
+    }
+}
+
+fn field_access(access: &RegisterBitfieldAccess) -> &'static str {
+    match access {
+        RegisterBitfieldAccess::R => "read-only",
+        RegisterBitfieldAccess::W => "write-only",
+        RegisterBitfieldAccess::RW => "read-write",
+    }
+}
+
+/// Format `value` as hexadecimal number with `width` bits.
+/// Digits of nibbles that are not fully covered by `mask` are shown as `?`.
+fn format_value(value: u64, mask: u64, width: u32) -> String {
+    let width = width.max(1);
+    let nibbles = width.div_ceil(4) as usize;
+    let value_mask = u64::MAX >> (64 - width);
+    if mask & value_mask == value_mask {
+        return format!("0x{:0nibbles$X}", value & value_mask);
+    }
+    let digits: String = (0..nibbles)
+        .rev()
+        .map(|nibble| {
+            let nibble_mask = (0xf << (nibble * 4)) & value_mask;
+            if mask & nibble_mask == nibble_mask {
+                char::from_digit(((value & nibble_mask) >> (nibble * 4)) as u32, 16)
+                    .unwrap()
+                    .to_ascii_uppercase()
+            } else {
+                '?'
+            }
+        })
+        .collect();
+    format!("0x{digits}")
+}
+
+impl DocManual {
+    pub fn new(ir: &IR) -> Self {
+        let peripheral_mod = &ir.device.peripheral_mod;
+        let link = |name: &str| DocLink {
+            name: name.to_owned(),
+            page: page_name(name),
+        };
+        let interrupt = |interrupt: &Interrupt| DocInterrupt {
+            name: interrupt.name.clone(),
+            value: interrupt.value,
+            description: interrupt.description.clone(),
+            peripheral: peripheral_mod
+                .contains_key(&interrupt.peripheral)
+                .then(|| link(&interrupt.peripheral)),
+        };
+        let peripherals = peripheral_mod
+            .iter()
+            .map(|(name, peripheral)| {
+                let peripheral = peripheral.borrow();
+                let base_addr = peripheral.base_addr.first().copied().unwrap_or_default();
+                let mut registers = Vec::new();
+                add_registers(
+                    &mut registers,
+                    &DocPath::default(),
+                    base_addr,
+                    &peripheral.registers,
+                    &peripheral.clusters,
+                );
+                DocPeripheral {
+                    name: name.clone(),
+                    description: peripheral.description.clone(),
+                    page: page_name(name),
+                    base_addr: peripheral.base_addr.clone(),
+                    derived_from: peripheral
+                        .derived_from
+                        .as_ref()
+                        .filter(|base| peripheral_mod.contains_key(*base))
+                        .map(|base| link(base)),
+                    derived_by: peripheral_mod
+                        .iter()
+                        .filter(|(_, other)| other.borrow().derived_from.as_ref() == Some(name))
+                        .map(|(other_name, _)| link(other_name))
+                        .collect(),
+                    registers,
+                    interrupts: peripheral.interrupts.iter().map(interrupt).collect(),
+                }
+            })
+            .collect();
+        DocManual {
+            name: ir.device.name.clone(),
+            description: ir.device.description.clone(),
+            peripherals,
+            interrupts: ir.interrupt_table.iter().flatten().map(interrupt).collect(),
+        }
+    }
+}
+
+fn add_registers(
+    doc_registers: &mut Vec<DocRegister>,
+    path: &DocPath,
+    base_addr: u64,
+    registers: &IndexMap<String, Rc<RefCell<Register>>>,
+    clusters: &IndexMap<String, Rc<RefCell<Cluster>>>,
+) {
+    for register in registers.values() {
+        let register = register.borrow();
+        let path = path.child(
+            &register.name,
+            register.offset,
+            register.dim,
+            register.dim_increment,
+            &register.dim_index,
+        );
+        doc_registers.push(doc_register(&register, path, base_addr));
+    }
+    for cluster in clusters.values() {
+        let cluster = cluster.borrow();
+        let path = path.child(
+            &cluster.name,
+            cluster.offset,
+            cluster.dim,
+            cluster.dim_increment,
+            &cluster.dim_index,
+        );
+        add_registers(
+            doc_registers,
+            &path,
+            base_addr,
+            &cluster.registers,
+            &cluster.clusters,
+        );
+    }
+}
+
+fn doc_register(register: &Register, path: DocPath, base_addr: u64) -> DocRegister {
+    let size = register.size.bits();
+    // Field arrays are expanded. Each entry is name, least significant bit and width.
+    let mut fields = Vec::new();
+    let mut layout = Vec::new();
+    for field in register.resolved_fields().values() {
+        let field = field.borrow();
+        let width = u32::BITS - field.mask.leading_zeros();
+        let enum_type_name = field
+            .enum_type_read
+            .as_ref()
+            .or(field.enum_type_write.as_ref());
+        let enum_type = field
+            .enum_types
+            .iter()
+            .find(|enum_type| Some(&enum_type.name) == enum_type_name);
+        for index in 0..field.dim {
+            let name = if field.dim == 1 {
+                field.name.clone()
+            } else {
+                field
+                    .dim_index
+                    .get(index as usize)
+                    .map_or_else(|| format!("{}{index}", field.name), |name| name.clone())
+            };
+            let lsb = field.offset + index * field.dim_increment;
+            let msb = lsb + width - 1;
+            let field_mask = u64::from(field.mask) << lsb;
+            fields.push(DocField {
+                name: name.clone(),
+                bits: if width == 1 {
+                    format!("[{lsb}]")
+                } else {
+                    format!("[{msb}:{lsb}]")
+                },
+                access: field_access(&field.access),
+                reset_value: format_value(
+                    (register.reset_value & field_mask) >> lsb,
+                    (register.reset_mask & field_mask) >> lsb,
+                    width,
+                ),
+                description: field.description.clone(),
+                values: enum_type
+                    .map(|enum_type| {
+                        enum_type
+                            .values
+                            .iter()
+                            .map(|value| DocEnumValue {
+                                name: value.name.clone(),
+                                value: value.value,
+                                description: value.description.clone(),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            });
+            layout.push((name, lsb, width));
+        }
+    }
+    DocRegister {
+        anchor: anchor(&path.name),
+        name: path.name,
+        offset: path.offset,
+        address: base_addr + path.offset,
+        arrays: path.arrays,
+        size,
+        access: register_access(&register.access),
+        reset_value: format_value(register.reset_value, register.reset_mask, size),
+        description: register.description.clone(),
+        fields,
+        bit_rows: bit_rows(size, &layout),
+    }
+}
+
+/// Split the bit diagram of a register in rows of [`BITS_PER_ROW`] bits
+fn bit_rows(size: u32, layout: &[(String, u32, u32)]) -> Vec<DocBitRow> {
+    // Owner of each bit. Overlapping bitfields are shown only once.
+    let mut owners: Vec<Option<usize>> = vec![None; size as usize];
+    for (index, (_, lsb, width)) in layout.iter().enumerate() {
+        for bit in *lsb..(*lsb + *width).min(size) {
+            owners[bit as usize].get_or_insert(index);
+        }
+    }
+    let row_size = BITS_PER_ROW.min(size);
+    (0..size / row_size)
+        .rev()
+        .map(|row| {
+            let bits: Vec<u32> = (row * row_size..(row + 1) * row_size).rev().collect();
+            let mut cells: Vec<DocBitCell> = Vec::new();
+            let mut previous_owner = None;
+            for (position, bit) in bits.iter().enumerate() {
+                let owner = owners[*bit as usize];
+                match cells.last_mut() {
+                    Some(cell) if position > 0 && owner == previous_owner => cell.width += 1,
+                    _ => cells.push(DocBitCell {
+                        name: owner.map_or_else(String::new, |index| layout[index].0.clone()),
+                        width: 1,
+                        reserved: owner.is_none(),
+                    }),
+                }
+                previous_owner = owner;
+            }
+            DocBitRow { bits, cells }
+        })
+        .collect()
+}
@@ -82,6 +82,10 @@ pub struct FieldGetterSetter {
     /// The same as enum_type_write but for read function
     pub enum_type_read: Option<String>,
     pub access: RegisterBitfieldAccess,
+    /// None if the bitfield inherits the value of the register
+    pub modified_write_values: Option<ModifiedWriteValues>,
+    /// None if the bitfield inherits the value of the register
+    pub read_action: Option<ReadAction>,
     pub dim: u32,
     pub dim_increment: u32,
     pub dim_index: Vec<String>,
@@ -103,6 +107,30 @@ pub enum RegisterBitfieldAccess {
     RW,
 }
 
+/// Effect of writing a register or bitfield, see `modifiedWriteValues` in SVD
+#[derive(Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ModifiedWriteValues {
+    OneToClear,
+    OneToSet,
+    OneToToggle,
+    ZeroToClear,
+    ZeroToSet,
+    ZeroToToggle,
+    Clear,
+    Set,
+    #[default]
+    Modify,
+}
+
+/// Side effect of reading a register or bitfield, see `readAction` in SVD
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReadAction {
+    Clear,
+    Set,
+    Modify,
+    ModifyExternal,
+}
+
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum BitSize {
     BIT64,
@@ -124,6 +152,35 @@ impl BitSize {
             BitSize::BIT64
         }
     }
+
+    /// Mask with all bits of the size set
+    pub fn mask(&self) -> u64 {
+        match self {
+            BitSize::BIT64 => u64::MAX,
+            BitSize::BIT32 => u32::MAX.into(),
+            BitSize::BIT16 => u16::MAX.into(),
+            BitSize::BIT8 => u8::MAX.into(),
+        }
+    }
+
+    /// Size in bits
+    pub fn bits(&self) -> u32 {
+        self.mask().count_ones()
+    }
+
+    /// Size in bytes
+    pub fn bytes(&self) -> u32 {
+        self.bits() / 8
+    }
+}
+
+/// Offsets of companion registers that set, clear or toggle the bits written as 1.
+/// Offsets are relative to the register that is modified.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AliasRegisters {
+    pub set: Option<i64>,
+    pub clear: Option<i64>,
+    pub toggle: Option<i64>,
 }
 
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -138,12 +195,55 @@ pub struct Register {
     pub fields: IndexMap<String, Rc<RefCell<FieldGetterSetter>>>,
     pub size: BitSize,
     pub reset_value: u64,
+    /// Bits of the register that have a defined reset value
+    pub reset_mask: u64,
+    /// Reading the register or one of its bitfields has side effects
+    pub has_read_action: bool,
+    /// Default effect of writing the bitfields
+    pub modified_write_values: ModifiedWriteValues,
+    /// Default side effect of reading the bitfields
+    pub read_action: Option<ReadAction>,
     pub has_enumerated_fields: bool,
     pub is_derived_from: bool,
+    /// Register is an alternate of another register at the same address (`alternateGroup` or `alternateRegister`)
+    pub is_alternate: bool,
     /// Full Rust path to module that contains the struct
     pub struct_module_path: Vec<String>,
     /// Id of the struct
     pub struct_id: String,
+    /// Companion SET/CLR/TGL registers
+    pub alias_registers: AliasRegisters,
+    /// Register referenced by `derivedFrom`. Serialized as the bitfields inherited from it.
+    #[serde(
+        rename = "inherited_fields",
+        serialize_with = "serialize_inherited_fields",
+        skip_deserializing
+    )]
+    pub derived_from: Option<Rc<RefCell<Register>>>,
+}
+
+impl Register {
+    /// Bitfields of the register. Derived registers without own bitfields have the bitfields
+    /// of the register they are derived from.
+    pub fn resolved_fields(&self) -> IndexMap<String, Rc<RefCell<FieldGetterSetter>>> {
+        match &self.derived_from {
+            Some(base_register) if self.fields.is_empty() => {
+                base_register.borrow().resolved_fields()
+            }
+            _ => self.fields.clone(),
+        }
+    }
+}
+
+fn serialize_inherited_fields<S: serde::Serializer>(
+    derived_from: &Option<Rc<RefCell<Register>>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    derived_from
+        .as_ref()
+        .map(|base_register| base_register.borrow().resolved_fields())
+        .unwrap_or_default()
+        .serialize(serializer)
 }
 
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -175,6 +275,8 @@ pub struct PeripheralMod {
     pub base_addr: Vec<u64>,
     pub interrupts: Vec<Interrupt>,
     pub derived_from: Option<String>,
+    /// Peripheral is an `alternatePeripheral` of another peripheral at the same address
+    pub is_alternate: bool,
     // Struct identifier of the peripheral.
     pub struct_id: String,
     // It could be different from name lower case if derived_from is used.
@@ -202,6 +304,11 @@ pub struct Interrupt {
     pub name: String,
     pub value: u32,
     pub description: String,
+    /// Name of the peripheral that defines the interrupt
+    pub peripheral: String,
+    /// Absolute address of the Aurix service request node (SRC register) of the interrupt.
+    /// None if no SRC register matches the name of the interrupt.
+    pub service_request_node: Option<u64>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
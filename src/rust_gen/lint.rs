@@ -0,0 +1,436 @@
+//! Checks of an SVD file for problems that lead to wrong or not compilable code.
+//!
+//! Names are checked on the SVD as written, layout checks are executed on the SVD with expanded arrays
+//! and resolved `derivedFrom`. Items are identified by their SVD path as written, e.g. `TIMER.Cluster1.CR.PSC`,
+//! so problems of arrays and derived peripherals are reported once.
+use super::util::ToSanitizedSymbol;
+use super::xml2ir::name_matches;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use svd_parser::svd;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Generated code is wrong, doesn't compile or the item is skipped
+    Error,
+    /// Generated code may not match the intention of the SVD author
+    Warning,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Identifier of the check, e.g. `overlapping-fields`
+    pub code: &'static str,
+    /// SVD path of the item, e.g. `PERIPHERAL.CLUSTER.REGISTER.FIELD`
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(
+            f,
+            "{severity}[{}] {}: {}",
+            self.code, self.path, self.message
+        )
+    }
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct LintReport {
+    pub diagnostics: Vec<Diagnostic>,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        write!(f, "{} errors, {} warnings", self.errors, self.warnings)
+    }
+}
+
+/// Register with absolute address used to detect overlaps
+struct RegisterSpan<'a> {
+    path: String,
+    address: u64,
+    bytes: u64,
+    register: &'a svd::RegisterInfo,
+}
+
+impl LintReport {
+    /// Check `device` as parsed from the SVD and `expanded_device`, the same device with
+    /// expanded arrays, resolved `derivedFrom` and propagated register properties.
+    pub fn new(device: &svd::Device, expanded_device: &svd::Device) -> Self {
+        let mut report = LintReport::default();
+        if device.license_text.is_none() {
+            report.push(
+                Severity::Warning,
+                "missing-license",
+                &device.name,
+                "no license defined. Use --license-file option when generating the PAC".to_owned(),
+            );
+        }
+        report.check_names(device);
+        let mut layout_report = LintReport::default();
+        for peripheral in &expanded_device.peripherals {
+            layout_report.check_peripheral_layout(peripheral);
+        }
+        for diagnostic in layout_report.diagnostics {
+            report.push_expanded(
+                device,
+                diagnostic.severity,
+                diagnostic.code,
+                &diagnostic.path,
+                diagnostic.message,
+            );
+        }
+        report.check_interrupts(expanded_device);
+        report
+    }
+
+    pub fn push(&mut self, severity: Severity, code: &'static str, path: &str, message: String) {
+        match severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+        }
+        self.diagnostics.push(Diagnostic {
+            severity,
+            code,
+            path: path.to_owned(),
+            message,
+        });
+    }
+
+    /// Add diagnostic of an item identified by `path` in the expanded device.
+    ///
+    /// The item is reported with its path in `device`. Diagnostics with the same code are reported once
+    /// for all elements of an array and for all peripherals derived from the same peripheral.
+    pub fn push_expanded(
+        &mut self,
+        device: &svd::Device,
+        severity: Severity,
+        code: &'static str,
+        path: &str,
+        message: String,
+    ) {
+        let path = svd_path(device, path);
+        if !self
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path)
+        {
+            self.push(severity, code, &path, message);
+        }
+    }
+
+    /// Check that names are valid identifiers and that sibling items don't get the same
+    /// identifier in generated code.
+    fn check_names(&mut self, device: &svd::Device) {
+        self.check_siblings(
+            &device.name,
+            device.peripherals.iter().map(|peripheral| &peripheral.name),
+        );
+        for peripheral in &device.peripherals {
+            self.check_register_cluster_names(
+                &peripheral.name,
+                peripheral.registers.as_deref().unwrap_or_default(),
+            );
+        }
+    }
+
+    fn check_register_cluster_names(&mut self, path: &str, children: &[svd::RegisterCluster]) {
+        // Registers of alternate groups are generated with group name as suffix
+        let names: Vec<String> = children
+            .iter()
+            .map(|child| match child {
+                svd::RegisterCluster::Register(register) => match &register.alternate_group {
+                    Some(group) => format!("{}_{group}", register.name),
+                    None => register.name.clone(),
+                },
+                svd::RegisterCluster::Cluster(cluster) => cluster.name.clone(),
+            })
+            .collect();
+        self.check_siblings(path, names.iter());
+        for (child, name) in children.iter().zip(&names) {
+            let child_path = format!("{path}.{name}");
+            match child {
+                svd::RegisterCluster::Register(register) => {
+                    self.check_siblings(&child_path, register.fields().map(|field| &field.name));
+                    for field in register.fields() {
+                        for enumerated_values in &field.enumerated_values {
+                            self.check_siblings(
+                                &format!("{child_path}.{}", field.name),
+                                enumerated_values.values.iter().map(|value| &value.name),
+                            );
+                        }
+                    }
+                }
+                svd::RegisterCluster::Cluster(cluster) => {
+                    self.check_register_cluster_names(&child_path, &cluster.children)
+                }
+            }
+        }
+    }
+
+    /// Report names that are sanitized and names that generate the same identifier
+    /// as a previous sibling. Identifiers are compared ignoring case.
+    fn check_siblings<'a>(&mut self, parent_path: &str, names: impl Iterator<Item = &'a String>) {
+        let mut identifiers: HashMap<String, &String> = HashMap::new();
+        for name in names {
+            let path = format!("{parent_path}.{name}");
+            let internal_name = name.to_internal_ident();
+            let valid_name = internal_name.remove_invalid_char();
+            if valid_name != internal_name {
+                self.push(
+                    Severity::Warning,
+                    "sanitized-name",
+                    &path,
+                    format!("invalid characters in name are replaced: `{valid_name}`"),
+                );
+            }
+            if let Some(other) = identifiers.insert(valid_name.to_lowercase(), name) {
+                self.push(
+                    Severity::Error,
+                    "duplicate-name",
+                    &path,
+                    format!("generates the same identifier as `{other}`"),
+                );
+            }
+        }
+    }
+
+    fn check_peripheral_layout(&mut self, peripheral: &svd::Peripheral) {
+        let mut registers = Vec::new();
+        collect_registers(
+            &peripheral.name,
+            peripheral.base_address,
+            peripheral.registers.as_deref().unwrap_or_default(),
+            &mut registers,
+        );
+        for register in &registers {
+            self.check_register(register);
+        }
+        self.check_register_overlaps(registers);
+    }
+
+    fn check_register(&mut self, span: &RegisterSpan) {
+        let register = span.register;
+        let size = register.properties.size.unwrap_or(32);
+        if register.properties.access.is_none() {
+            self.push(
+                Severity::Warning,
+                "inferred-access",
+                &span.path,
+                "no access defined, access is inferred from bitfields".to_owned(),
+            );
+        }
+        let mut previous_fields: Vec<(&svd::Field, u64)> = Vec::new();
+        for field in register.fields() {
+            let path = format!("{}.{}", span.path, field.name);
+            let offset = field.bit_range.offset;
+            let width = field.bit_range.width;
+            if field.access.is_none() {
+                self.push(
+                    Severity::Warning,
+                    "missing-access",
+                    &path,
+                    "no access defined, bitfield is skipped".to_owned(),
+                );
+            }
+            if offset + width > size {
+                self.push(
+                    Severity::Error,
+                    "field-out-of-range",
+                    &path,
+                    format!(
+                        "bits {offset}..{} exceed register size of {size} bits",
+                        offset + width
+                    ),
+                );
+            }
+            let mask = bit_mask(width).checked_shl(offset).unwrap_or(0);
+            for (other, other_mask) in &previous_fields {
+                if mask & other_mask != 0 {
+                    self.push(
+                        Severity::Error,
+                        "overlapping-fields",
+                        &path,
+                        format!("overlaps bitfield `{}`", other.name),
+                    );
+                }
+            }
+            previous_fields.push((field, mask));
+            for value in field
+                .enumerated_values
+                .iter()
+                .flat_map(|enumerated_values| &enumerated_values.values)
+            {
+                let Some(value_number) = value.value else {
+                    continue;
+                };
+                if value_number & !bit_mask(width) != 0 {
+                    self.push(
+                        Severity::Error,
+                        "enum-value-out-of-range",
+                        &format!("{path}.{}", value.name),
+                        format!("value {value_number:#x} doesn't fit in {width} bits"),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Report registers that overlap a register at lower address unless
+    /// one of them is declared as alternate register.
+    fn check_register_overlaps(&mut self, mut registers: Vec<RegisterSpan>) {
+        registers.sort_by_key(|register| register.address);
+        let mut last_end: Option<(u64, usize)> = None;
+        for (index, register) in registers.iter().enumerate() {
+            if let Some((end, other_index)) = last_end {
+                let other = &registers[other_index];
+                if register.address < end && !is_alternate(register) && !is_alternate(other) {
+                    self.push(
+                        Severity::Warning,
+                        "overlapping-registers",
+                        &register.path,
+                        format!(
+                            "overlaps register `{}` at address {:#x} and is not declared as alternate register",
+                            other.path, register.address
+                        ),
+                    );
+                }
+            }
+            let end = register.address + register.bytes;
+            if last_end.is_none_or(|(last, _)| end > last) {
+                last_end = Some((end, index));
+            }
+        }
+    }
+
+    /// Interrupts with the same value and different names
+    fn check_interrupts(&mut self, device: &svd::Device) {
+        let mut interrupts: HashMap<u32, &str> = HashMap::new();
+        for peripheral in &device.peripherals {
+            for interrupt in &peripheral.interrupt {
+                match interrupts.get(&interrupt.value) {
+                    Some(name) if *name != interrupt.name => self.push(
+                        Severity::Warning,
+                        "duplicated-interrupt",
+                        &format!("{}.{}", peripheral.name, interrupt.name),
+                        format!(
+                            "interrupt number {} is already used by `{name}`",
+                            interrupt.value
+                        ),
+                    ),
+                    Some(_) => {}
+                    None => {
+                        interrupts.insert(interrupt.value, &interrupt.name);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn collect_registers<'a>(
+    path: &str,
+    address: u64,
+    children: &'a [svd::RegisterCluster],
+    registers: &mut Vec<RegisterSpan<'a>>,
+) {
+    for child in children {
+        match child {
+            svd::RegisterCluster::Register(register) => registers.push(RegisterSpan {
+                path: format!("{path}.{}", register.name),
+                address: address + register.address_offset as u64,
+                bytes: register.properties.size.unwrap_or(32).div_ceil(8) as u64,
+                register,
+            }),
+            svd::RegisterCluster::Cluster(cluster) => collect_registers(
+                &format!("{path}.{}", cluster.name),
+                address + cluster.address_offset as u64,
+                &cluster.children,
+                registers,
+            ),
+        }
+    }
+}
+
+/// Path in `device` of the item with `expanded_path` in the expanded device.
+///
+/// Elements of arrays are mapped to the array and registers of derived peripherals without own registers
+/// to the registers of the base peripheral. The part of the path not found in `device` is kept unchanged.
+fn svd_path(device: &svd::Device, expanded_path: &str) -> String {
+    let mut segments = expanded_path.split('.');
+    let Some(peripheral_name) = segments.next() else {
+        return expanded_path.to_owned();
+    };
+    let find_peripheral = |expanded_name: &str| {
+        device
+            .peripherals
+            .iter()
+            .find(|peripheral| name_matches(&peripheral.name, expanded_name))
+    };
+    let Some(mut peripheral) = find_peripheral(peripheral_name) else {
+        return expanded_path.to_owned();
+    };
+    if let (None, Some(base)) = (&peripheral.registers, &peripheral.derived_from) {
+        peripheral = find_peripheral(base).unwrap_or(peripheral);
+    }
+    let mut path = vec![peripheral.name.as_str()];
+    let mut children = peripheral.registers.as_deref().unwrap_or_default();
+    let mut fields: &[svd::Field] = &[];
+    for expanded_name in segments.by_ref() {
+        let child = children
+            .iter()
+            .find(|child| name_matches(child.name(), expanded_name));
+        let field = fields
+            .iter()
+            .find(|field| name_matches(&field.name, expanded_name));
+        match (child, field) {
+            (Some(svd::RegisterCluster::Cluster(cluster)), _) => {
+                path.push(&cluster.name);
+                children = &cluster.children;
+                fields = &[];
+            }
+            (Some(svd::RegisterCluster::Register(register)), _) => {
+                path.push(&register.name);
+                children = &[];
+                fields = register.fields.as_deref().unwrap_or_default();
+            }
+            (None, Some(field)) => {
+                path.push(&field.name);
+                children = &[];
+                fields = &[];
+            }
+            (None, None) => {
+                path.push(expanded_name);
+                break;
+            }
+        }
+    }
+    path.extend(segments);
+    path.join(".")
+}
+
+fn is_alternate(span: &RegisterSpan) -> bool {
+    span.register.alternate_group.is_some() || span.register.alternate_register.is_some()
+}
+
+fn bit_mask(width: u32) -> u64 {
+    if width >= u64::BITS {
+        u64::MAX
+    } else {
+        (1u64 << width) - 1
+    }
+}
@@ -1,4 +1,6 @@
-mod svd2temp;
+mod register_addresses;
+mod source_location;
+mod vendor_extensions;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -11,10 +13,14 @@ use crate::svd_util::*;
 use anyhow::Ok;
 use anyhow::Result;
 use indexmap::IndexMap;
+use lazy_regex::regex;
 use log::{debug, error, warn};
+use register_addresses::*;
 use svd_parser::svd;
 use svd_parser::svd::Name;
-use svd2temp::*;
+
+pub(super) use source_location::{add_source_location, locate_errors, name_matches};
+pub(super) use vendor_extensions::parse_vendor_extensions;
 
 trait RegisterHelper {
     /// Get name of register considering the presence of alternate group
@@ -120,6 +126,10 @@ struct Visitor {
     current_item_svd_path: Vec<String>,
     // Path to the module of item in Rust code
     current_mod_ir_path: Vec<String>,
+    // Detect companion SET/CLR/TGL registers
+    alias_registers: bool,
+    // Errors of skipped items
+    errors: Vec<ParseError>,
 }
 impl Visitor {
     /// Create the intermediate representation of device used by template engine
@@ -128,66 +138,72 @@ impl Visitor {
         self.device.description.clone_from(&device.description);
 
         for svd_peripheral in device.peripherals.iter() {
-            let derived_peripheral: Option<PeripheralMod> =
-                // Check if the peripheral is derived from another one
-                if let Some(derived_ref) = &svd_peripheral.derived_from {
-                     // If so get the reference to the original peripheral
-                    if let Some(ref_item) = self.svd_ref_to_ir_item.get(derived_ref) {
-                        // Check if the reference is a peripheral
-                        match ref_item {
-                            DeviceItem::Peripheral(ref_peripheral) => {
-                                Some(ref_peripheral.borrow().clone())
-                            }
-                            // if it is not a peripheral return an error
-                            _ => {
-                                return Err(ParseError::InvalidPeripheral {
-                                    peripheral_name: svd_peripheral.name.clone(),
-                                    msg: format!(
-                                        "reference {derived_ref} doesn't point to a peripheral"
-                                    ),
-                                }
-                                .into());
+            self.visit_item(|visitor| visitor.add_peripheral(svd_peripheral))?;
+        }
+        Ok(())
+    }
+
+    /// Create the intermediate representation of a peripheral and add it to the device
+    fn add_peripheral(&mut self, svd_peripheral: &svd::Peripheral) -> Result<()> {
+        // Push the peripheral svd and ir path in corresponding FIFO stack
+        self.push_current_item_svd_path(svd_peripheral)?;
+        let derived_peripheral: Option<PeripheralMod> =
+            // Check if the peripheral is derived from another one
+            if let Some(derived_ref) = &svd_peripheral.derived_from {
+                 // If so get the reference to the original peripheral
+                if let Some(ref_item) = self.svd_ref_to_ir_item.get(derived_ref) {
+                    // Check if the reference is a peripheral
+                    match ref_item {
+                        DeviceItem::Peripheral(ref_peripheral) => {
+                            Some(ref_peripheral.borrow().clone())
+                        }
+                        // if it is not a peripheral return an error
+                        _ => {
+                            return Err(ParseErrorKind::InvalidPeripheral {
+                                peripheral_name: svd_peripheral.name.clone(),
+                                msg: format!(
+                                    "reference {derived_ref} doesn't point to a peripheral"
+                                ),
                             }
+                            .into());
                         }
-                    } else {
-                        // If the reference is not found return an error
-                        return Err(ParseError::InvalidPeripheral {
-                            peripheral_name: svd_peripheral.name.clone(),
-                            msg: format!("Missing reference {derived_ref}"),
-                        }.into());
                     }
                 } else {
-                    None
-                };
-
-            // Push the peripheral svd and ir path in corresponding FIFO stack
-            self.push_current_item_svd_path(svd_peripheral)?;
-            // If derivedFrom point to some peripheral get a clone of this peripheral
-            // other wise create a new one
-            let mut peripheral = derived_peripheral
-                .as_ref()
-                .map_or_else(PeripheralMod::default, |x| x.clone());
-            // Update the peripheral_mod with data from svd::peripheral
-            self.visit_peripheral(svd_peripheral, &mut peripheral)?;
-
-            let name = peripheral.name.clone();
-
-            peripheral.derived_from = if let Some(derived_peri) = derived_peripheral {
-                if peripheral.has_same_type(&derived_peri) {
-                    Some(derived_peri.name)
-                } else {
-                    None
+                    // If the reference is not found return an error
+                    return Err(ParseErrorKind::InvalidPeripheral {
+                        peripheral_name: svd_peripheral.name.clone(),
+                        msg: format!("Missing reference {derived_ref}"),
+                    }.into());
                 }
             } else {
                 None
             };
-            let peripheral_mod = Rc::new(RefCell::new(peripheral));
-            self.device
-                .peripheral_mod
-                .insert(name, peripheral_mod.clone());
-            // Pop out the paths and the just updated peripheral in svd to it index
-            self.pop_current_item_svd_path(DeviceItem::Peripheral(peripheral_mod));
-        }
+
+        // If derivedFrom point to some peripheral get a clone of this peripheral
+        // other wise create a new one
+        let mut peripheral = derived_peripheral
+            .as_ref()
+            .map_or_else(PeripheralMod::default, |x| x.clone());
+        // Update the peripheral_mod with data from svd::peripheral
+        self.visit_peripheral(svd_peripheral, &mut peripheral)?;
+
+        let name = peripheral.name.clone();
+
+        peripheral.derived_from = if let Some(derived_peri) = derived_peripheral {
+            if peripheral.has_same_type(&derived_peri) {
+                Some(derived_peri.name)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        let peripheral_mod = Rc::new(RefCell::new(peripheral));
+        self.device
+            .peripheral_mod
+            .insert(name, peripheral_mod.clone());
+        // Pop out the paths and the just updated peripheral in svd to it index
+        self.pop_current_item_svd_path(DeviceItem::Peripheral(peripheral_mod));
         Ok(())
     }
     fn visit_peripheral(
@@ -195,9 +211,10 @@ impl Visitor {
         svd_peripheral: &svd::Peripheral,
         peripheral: &mut PeripheralMod,
     ) -> Result<()> {
-        debug!("Parsing peripheral: {}", &svd_peripheral.name);
+        debug!("Parsing peripheral: {}", svd_peripheral.name);
         peripheral.name = svd_peripheral.name.to_internal_ident();
         peripheral.description = svd_peripheral.description.clone().unwrap_or_default();
+        peripheral.is_alternate = svd_peripheral.alternate_peripheral.is_some();
 
         // defined headerStructName has priority for struct id definition.
         if let Some(header_struct) = &svd_peripheral.header_struct_name {
@@ -227,14 +244,22 @@ impl Visitor {
                     .description
                     .as_ref()
                     .map_or_else(String::new, |x| x.clone()),
+                peripheral: peripheral.name.clone(),
+                service_request_node: None,
             })
             .collect();
 
-        for cluster_register in svd_peripheral.registers.as_ref().unwrap_or(&Vec::new()) {
-            self.visit_cluster_register(
-                cluster_register,
-                PeripheralClusterE::Peripheral(peripheral),
-            )?;
+        let svd_registers = svd_peripheral.registers.as_deref().unwrap_or_default();
+        for cluster_register in svd_registers {
+            self.visit_item(|visitor| {
+                visitor.visit_cluster_register(
+                    cluster_register,
+                    PeripheralClusterE::Peripheral(peripheral),
+                )
+            })?;
+        }
+        if self.alias_registers {
+            link_alias_registers(svd_registers, &peripheral.registers);
         }
         Ok(())
     }
@@ -248,6 +273,7 @@ impl Visitor {
         register.name = reg.get_name_id_internal();
         register.description = reg.description.clone().unwrap_or_default();
         register.offset = reg.address_offset;
+        register.is_alternate = reg.alternate_group.is_some() || reg.alternate_register.is_some();
         (register.dim, register.dim_increment, register.dim_index) = get_dim_dim_increment(reg);
 
         if register.struct_id.is_empty() {
@@ -259,66 +285,17 @@ impl Visitor {
         }
         // Get fields
         let mut fields = Vec::new();
-        for field in reg.fields() {
-            if field.derived_from.is_some() {
-                return Err(ParseError::Unsupported(
-                    "derived_from is not supported in field".to_string(),
-                )
-                .into());
-            }
-            let description = field.description.clone().unwrap_or_default();
-            let offset = field.bit_range.offset;
-            let mask = (0..field.bit_range.width - 1).fold(0x1u32, |acc, _| (acc << 1) | 0x1);
-            let name = field.name.to_internal_ident();
-            let svd_field_access = match field.access {
-                None => {
-                    error!(
-                        "Inheritance of access is not supported. Bitfield: {} access shall be specified. Bitfield skipped",
-                        name
-                    );
-                    continue;
-                }
-                Some(acc) => acc,
-            };
-            let access = match svd_field_access {
-                svd::Access::ReadOnly => RegisterBitfieldAccess::R,
-                svd::Access::WriteOnly => RegisterBitfieldAccess::W,
-                svd::Access::ReadWrite => RegisterBitfieldAccess::RW,
-                svd::Access::WriteOnce => RegisterBitfieldAccess::W,
-                svd::Access::ReadWriteOnce => RegisterBitfieldAccess::RW,
-            };
-
-            let (dim, dim_increment, dim_index) = get_dim_dim_increment(field);
-            let enum_types = get_values_types(field)?;
-            let enum_type_write = enum_types
-                .iter()
-                .find(|x| {
-                    x.usage == EnumeratedValueUsage::Write
-                        || x.usage == EnumeratedValueUsage::ReadWrite
-                })
-                .map(|x| x.name.clone());
-            let enum_type_read = enum_types
-                .iter()
-                .find(|x| {
-                    x.usage == EnumeratedValueUsage::Read
-                        || x.usage == EnumeratedValueUsage::ReadWrite
-                })
-                .map(|x| x.name.clone());
-
-            fields.push(FieldGetterSetter {
-                name,
-                description,
-                offset,
-                mask,
-                enum_types,
-                enum_type_write,
-                enum_type_read,
-                access,
-                size: BitSize::val_2_bit_size(mask.into()),
-                dim,
-                dim_increment,
-                dim_index,
-            });
+        for svd_field in reg.fields() {
+            let field = self.visit_item(|visitor| {
+                // Path of field is used only to report errors
+                visitor
+                    .current_item_svd_path
+                    .push(svd_field.get_expanded_name()?);
+                let field = get_field(svd_field)?;
+                visitor.current_item_svd_path.pop();
+                Ok(field)
+            })?;
+            fields.extend(field.flatten());
         }
         match reg.properties.size {
             Some(value) => {
@@ -328,7 +305,7 @@ impl Visitor {
                     16 => BitSize::BIT16,
                     8 => BitSize::BIT8,
                     register_size => {
-                        return Err(ParseError::Unsupported(format!(
+                        return Err(ParseErrorKind::Unsupported(format!(
                             "Unsupported register size {register_size}"
                         ))
                         .into());
@@ -337,7 +314,7 @@ impl Visitor {
             }
             None => {
                 if reg.derived_from.is_none() {
-                    return Err(ParseError::InvalidRegister {
+                    return Err(ParseErrorKind::InvalidRegister {
                         register_name: reg.name.clone(),
                         msg: "register is not derived and it has no specified size".to_string(),
                     }
@@ -349,7 +326,7 @@ impl Visitor {
             Some(value) => register.reset_value = value,
             None => {
                 if reg.derived_from.is_none() {
-                    return Err(ParseError::InvalidRegister {
+                    return Err(ParseErrorKind::InvalidRegister {
                         register_name: register.name.clone(),
                         msg: "register is not derived and it has no specified reset value"
                             .to_string(),
@@ -359,6 +336,26 @@ impl Visitor {
             }
         }
 
+        match reg.properties.reset_mask {
+            Some(value) => register.reset_mask = value,
+            // By default all bits of the register have a defined reset value
+            None => {
+                if reg.derived_from.is_none() {
+                    register.reset_mask = register.size.mask();
+                }
+            }
+        }
+
+        // Derived registers inherit the read side effects of the original register
+        register.has_read_action |=
+            reg.read_action.is_some() || reg.fields().any(|f| f.read_action.is_some());
+        if let Some(modified_write_values) = reg.modified_write_values {
+            register.modified_write_values = get_modified_write_values(modified_write_values);
+        }
+        if let Some(read_action) = reg.read_action {
+            register.read_action = Some(get_read_action(read_action));
+        }
+
         register.has_enumerated_fields = fields.iter().any(|f| !f.enum_types.is_empty());
 
         match reg.properties.access {
@@ -377,7 +374,7 @@ impl Visitor {
                 if reg.derived_from.is_none() {
                     warn!(
                         "Access mode is not defined for register ({}) inferring from bitfield",
-                        &register.name
+                        register.name
                     );
                     let is_register_writable = fields.iter().any(|f| {
                         f.access == RegisterBitfieldAccess::W
@@ -394,7 +391,7 @@ impl Visitor {
                         (false, false) => {
                             error!(
                                 "No bitfield in register '{}' specifies an access mode. Not able to infer register access mode",
-                                &register.name
+                                register.name
                             );
                             RegisterAccess::R
                         }
@@ -433,7 +430,13 @@ impl Visitor {
         // defined in this cluster
         cluster.module_id = self.current_mod_ir_path.last().unwrap().clone();
         for cluster_register in &cluster_svd.children {
-            self.visit_cluster_register(cluster_register, PeripheralClusterE::Cluster(cluster))?;
+            self.visit_item(|visitor| {
+                visitor
+                    .visit_cluster_register(cluster_register, PeripheralClusterE::Cluster(cluster))
+            })?;
+        }
+        if self.alias_registers {
+            link_alias_registers(&cluster_svd.children, &cluster.registers);
         }
         Ok(())
     }
@@ -444,45 +447,53 @@ impl Visitor {
     ) -> Result<()> {
         match register_cluster {
             svd::RegisterCluster::Register(reg_svd) => {
-                let derived_register: Option<Register> = if let Some(derived_ref) =
-                    register_cluster.derived_from()
-                {
-                    let absolute_reference_path = self.get_absolute_svd_path(derived_ref);
-                    if let Some(ref_item) = self.svd_ref_to_ir_item.get(&absolute_reference_path) {
-                        if let DeviceItem::Register(ref_register) = ref_item {
-                            Some(ref_register.borrow().clone())
+                // References are relative to the parent of the register
+                let derived_ref = register_cluster
+                    .derived_from()
+                    .as_deref()
+                    .map(|derived_ref| (derived_ref, self.get_absolute_svd_path(derived_ref)));
+                // Push the target register svd and ir path in corresponding FIFO stack
+                self.push_current_item_svd_path(reg_svd)?;
+                let derived_register: Option<Rc<RefCell<Register>>> =
+                    if let Some((derived_ref, absolute_reference_path)) = derived_ref {
+                        if let Some(ref_item) =
+                            self.svd_ref_to_ir_item.get(&absolute_reference_path)
+                        {
+                            if let DeviceItem::Register(ref_register) = ref_item {
+                                Some(ref_register.clone())
+                            } else {
+                                return Err(ParseErrorKind::InvalidRegister {
+                                    register_name: reg_svd.name.clone(),
+                                    msg: format!(
+                                        "reference {derived_ref} doesn't point to register svd item"
+                                    ),
+                                }
+                                .into());
+                            }
                         } else {
-                            return Err(ParseError::InvalidRegister {
+                            return Err(ParseErrorKind::InvalidRegister {
                                 register_name: reg_svd.name.clone(),
-                                msg: format!(
-                                    "reference {derived_ref} doesn't point to register svd item"
-                                ),
+                                msg: format!("Missing reference {derived_ref}"),
                             }
                             .into());
                         }
                     } else {
-                        return Err(ParseError::InvalidRegister {
-                            register_name: reg_svd.name.clone(),
-                            msg: format!("Missing reference {derived_ref}"),
-                        }
-                        .into());
-                    }
-                } else {
-                    None
-                };
-                // Push the target register svd and ir path in corresponding FIFO stack
-                self.push_current_item_svd_path(reg_svd)?;
+                        None
+                    };
                 let mut register = derived_register
                     .as_ref()
-                    .map_or_else(Register::default, |x| x.clone());
+                    .map_or_else(Register::default, |x| x.borrow().clone());
 
                 self.visit_register(reg_svd, &mut register)?;
 
                 let name = register.name.clone();
                 // If after visiting the svd node and updating the cluster_svd we get cluster that has the same type
                 // set derived_register and replace the struct id
-                register.is_derived_from = derived_register
-                    .is_some_and(|derived_register| register.has_same_type(&derived_register));
+                register.is_derived_from =
+                    derived_register.as_ref().is_some_and(|derived_register| {
+                        register.has_same_type(&derived_register.borrow())
+                    });
+                register.derived_from = derived_register;
 
                 let register = Rc::new(RefCell::new(register));
 
@@ -493,35 +504,40 @@ impl Visitor {
                 self.pop_current_item_svd_path(DeviceItem::Register(register));
             }
             svd::RegisterCluster::Cluster(cluster_svd) => {
-                let derived_cluster: Option<Cluster> = if let Some(derived_ref) =
-                    register_cluster.derived_from()
-                {
-                    let absolute_reference_path = self.get_absolute_svd_path(derived_ref);
-                    if let Some(ref_item) = self.svd_ref_to_ir_item.get(&absolute_reference_path) {
-                        if let DeviceItem::Cluster(ref_cluster) = ref_item {
-                            Some(ref_cluster.borrow().clone())
+                // References are relative to the parent of the cluster
+                let derived_ref = register_cluster
+                    .derived_from()
+                    .as_deref()
+                    .map(|derived_ref| (derived_ref, self.get_absolute_svd_path(derived_ref)));
+                // Push the target cluster svd and ir path in corresponding FIFO stack
+                self.push_current_item_svd_path(cluster_svd)?;
+                let derived_cluster: Option<Cluster> =
+                    if let Some((derived_ref, absolute_reference_path)) = derived_ref {
+                        if let Some(ref_item) =
+                            self.svd_ref_to_ir_item.get(&absolute_reference_path)
+                        {
+                            if let DeviceItem::Cluster(ref_cluster) = ref_item {
+                                Some(ref_cluster.borrow().clone())
+                            } else {
+                                return Err(ParseErrorKind::InvalidCluster {
+                                    cluster_name: cluster_svd.name.clone(),
+                                    msg: format!(
+                                        "reference {derived_ref} doesn't point to cluster svd item"
+                                    ),
+                                }
+                                .into());
+                            }
                         } else {
-                            return Err(ParseError::InvalidCluster {
+                            return Err(ParseErrorKind::InvalidCluster {
                                 cluster_name: cluster_svd.name.clone(),
-                                msg: format!(
-                                    "reference {derived_ref} doesn't point to cluster svd item"
-                                ),
+                                msg: format!("Missing reference {derived_ref}"),
                             }
                             .into());
                         }
                     } else {
-                        return Err(ParseError::InvalidCluster {
-                            cluster_name: cluster_svd.name.clone(),
-                            msg: format!("Missing reference {derived_ref}"),
-                        }
-                        .into());
-                    }
-                } else {
-                    None
-                };
+                        None
+                    };
 
-                // Push the target cluster svd and ir path in corresponding FIFO stack
-                self.push_current_item_svd_path(cluster_svd)?;
                 let mut cluster = derived_cluster
                     .as_ref()
                     .map_or_else(Cluster::default, |x| x.clone());
@@ -543,6 +559,26 @@ impl Visitor {
         Ok(())
     }
 
+    /// Visit an SVD item with `visit`.
+    ///
+    /// In case of [`ParseErrorKind`] the error is recorded with the path of the item,
+    /// the item is skipped and `None` is returned. Other errors are returned.
+    fn visit_item<T>(&mut self, visit: impl FnOnce(&mut Self) -> Result<T>) -> Result<Option<T>> {
+        let svd_path_len = self.current_item_svd_path.len();
+        let mod_path_len = self.current_mod_ir_path.len();
+        let err = match visit(self) {
+            Err(err) => err,
+            result => return result.map(Some),
+        };
+        let kind = err.downcast::<ParseErrorKind>()?;
+        // In case of error the path points to the item that caused the error
+        self.errors
+            .push(ParseError::new(kind, self.current_item_svd_path.join(".")));
+        self.current_item_svd_path.truncate(svd_path_len);
+        self.current_mod_ir_path.truncate(mod_path_len);
+        Ok(None)
+    }
+
     fn pop_current_item_svd_path(&mut self, ir_item: DeviceItem) {
         // Item is already created. Add to map that support
         // cross references. e.g. derivedFrom attribute
@@ -586,6 +622,174 @@ impl Visitor {
     }
 }
 
+/// Name suffixes of companion registers that set the bits written as 1
+const SET_ALIAS_SUFFIXES: &[&str] = &["_SET", "SET"];
+/// Name suffixes of companion registers that clear the bits written as 1
+const CLEAR_ALIAS_SUFFIXES: &[&str] = &["_CLEAR", "_CLR", "CLR"];
+/// Name suffixes of companion registers that toggle the bits written as 1
+const TOGGLE_ALIAS_SUFFIXES: &[&str] = &["_TGL", "TGL", "_TOG", "_INV", "INV"];
+
+/// Accessor of the offset of a companion register kind in [`AliasRegisters`]
+type AliasOffsetAccessor = fn(&mut AliasRegisters) -> &mut Option<i64>;
+/// Kinds of companion registers: name suffixes, name of kind and accessor of offset
+const ALIAS_REGISTER_KINDS: [(&[&str], &str, AliasOffsetAccessor); 3] = [
+    (SET_ALIAS_SUFFIXES, "SET", |alias| &mut alias.set),
+    (CLEAR_ALIAS_SUFFIXES, "CLR", |alias| &mut alias.clear),
+    (TOGGLE_ALIAS_SUFFIXES, "TGL", |alias| &mut alias.toggle),
+];
+
+/// Detect companion SET/CLR/TGL registers of a peripheral or cluster and store their relative offset
+/// in the register that they modify.
+///
+/// A register is a companion of register `X` if its name is `X` followed by one of the known suffixes
+/// separated by `_` (e.g. `X_SET`, `X_CLR`, `X_TGL`). If `alternateRegister` is specified, it is used
+/// as name of `X` and suffixes without `_` are accepted too (e.g. `XCLR`). Otherwise names like `RESET`
+/// would be companions of `RE`.
+/// Companion registers shall be writable and have the same size and array dimension of `X`.
+fn link_alias_registers(
+    svd_registers: &[svd::RegisterCluster],
+    registers: &IndexMap<String, Rc<RefCell<Register>>>,
+) {
+    let svd_registers: Vec<&svd::Register> = svd_registers
+        .iter()
+        .filter_map(|register_cluster| match register_cluster {
+            svd::RegisterCluster::Register(register) => Some(register),
+            svd::RegisterCluster::Cluster(_) => None,
+        })
+        .collect();
+    for alias_svd in &svd_registers {
+        let name = alias_svd.name.to_uppercase();
+        for (suffixes, alias_kind, alias_offset) in ALIAS_REGISTER_KINDS {
+            let Some(suffix) = suffixes.iter().find(|suffix| {
+                name.ends_with(*suffix)
+                    && (suffix.starts_with('_') || alias_svd.alternate_register.is_some())
+            }) else {
+                continue;
+            };
+            let base_name = alias_svd.alternate_register.clone().unwrap_or_else(|| {
+                alias_svd.name[..alias_svd.name.len() - suffix.len()].to_string()
+            });
+            let Some(base_svd) = svd_registers
+                .iter()
+                .find(|reg| reg.name.eq_ignore_ascii_case(&base_name))
+            else {
+                continue;
+            };
+            let (Some(base), Some(alias)) = (
+                registers.get(&base_svd.get_name_id_internal()),
+                registers.get(&alias_svd.get_name_id_internal()),
+            ) else {
+                continue;
+            };
+            if Rc::ptr_eq(base, alias) {
+                continue;
+            }
+            let alias = alias.borrow();
+            let mut base = base.borrow_mut();
+            if alias.access == RegisterAccess::R
+                || alias.size != base.size
+                || alias.dim != base.dim
+                || alias.dim_increment != base.dim_increment
+            {
+                warn!(
+                    "Register {} is not compatible with {} register of {}. Shall be writable and have same size and dimension. Ignored",
+                    alias.name, alias_kind, base.name
+                );
+                continue;
+            }
+            *alias_offset(&mut base.alias_registers) =
+                Some(alias.offset as i64 - base.offset as i64);
+            break;
+        }
+    }
+}
+
+/// Create the bitfield of a register. Bitfields without access are skipped and `None` is returned.
+fn get_field(field: &svd::Field) -> Result<Option<FieldGetterSetter>> {
+    if field.derived_from.is_some() {
+        return Err(ParseErrorKind::Unsupported(
+            "derived_from is not supported in field".to_string(),
+        )
+        .into());
+    }
+    let description = field.description.clone().unwrap_or_default();
+    let offset = field.bit_range.offset;
+    let mask = (0..field.bit_range.width - 1).fold(0x1u32, |acc, _| (acc << 1) | 0x1);
+    let name = field.name.to_internal_ident();
+    let svd_field_access = match field.access {
+        None => {
+            error!(
+                "Inheritance of access is not supported. Bitfield: {} access shall be specified. Bitfield skipped",
+                name
+            );
+            return Ok(None);
+        }
+        Some(acc) => acc,
+    };
+    let access = match svd_field_access {
+        svd::Access::ReadOnly => RegisterBitfieldAccess::R,
+        svd::Access::WriteOnly => RegisterBitfieldAccess::W,
+        svd::Access::ReadWrite => RegisterBitfieldAccess::RW,
+        svd::Access::WriteOnce => RegisterBitfieldAccess::W,
+        svd::Access::ReadWriteOnce => RegisterBitfieldAccess::RW,
+    };
+
+    let (dim, dim_increment, dim_index) = get_dim_dim_increment(field);
+    let enum_types = get_values_types(field)?;
+    let enum_type_write = enum_types
+        .iter()
+        .find(|x| {
+            x.usage == EnumeratedValueUsage::Write || x.usage == EnumeratedValueUsage::ReadWrite
+        })
+        .map(|x| x.name.clone());
+    let enum_type_read = enum_types
+        .iter()
+        .find(|x| {
+            x.usage == EnumeratedValueUsage::Read || x.usage == EnumeratedValueUsage::ReadWrite
+        })
+        .map(|x| x.name.clone());
+
+    Ok(Some(FieldGetterSetter {
+        name,
+        description,
+        offset,
+        mask,
+        enum_types,
+        enum_type_write,
+        enum_type_read,
+        access,
+        modified_write_values: field.modified_write_values.map(get_modified_write_values),
+        read_action: field.read_action.map(get_read_action),
+        size: BitSize::val_2_bit_size(mask.into()),
+        dim,
+        dim_increment,
+        dim_index,
+    }))
+}
+
+fn get_modified_write_values(value: svd::ModifiedWriteValues) -> ModifiedWriteValues {
+    match value {
+        svd::ModifiedWriteValues::OneToClear => ModifiedWriteValues::OneToClear,
+        svd::ModifiedWriteValues::OneToSet => ModifiedWriteValues::OneToSet,
+        svd::ModifiedWriteValues::OneToToggle => ModifiedWriteValues::OneToToggle,
+        svd::ModifiedWriteValues::ZeroToClear => ModifiedWriteValues::ZeroToClear,
+        svd::ModifiedWriteValues::ZeroToSet => ModifiedWriteValues::ZeroToSet,
+        svd::ModifiedWriteValues::ZeroToToggle => ModifiedWriteValues::ZeroToToggle,
+        svd::ModifiedWriteValues::Clear => ModifiedWriteValues::Clear,
+        svd::ModifiedWriteValues::Set => ModifiedWriteValues::Set,
+        svd::ModifiedWriteValues::Modify => ModifiedWriteValues::Modify,
+    }
+}
+
+fn get_read_action(value: svd::ReadAction) -> ReadAction {
+    match value {
+        svd::ReadAction::Clear => ReadAction::Clear,
+        svd::ReadAction::Set => ReadAction::Set,
+        svd::ReadAction::Modify => ReadAction::Modify,
+        svd::ReadAction::ModifyExternal => ReadAction::ModifyExternal,
+    }
+}
+
 fn get_values_types(field: &svd::Field) -> Result<Vec<EnumeratedValueType>> {
     if field.enumerated_values.is_empty() {
         return Ok(vec![]);
@@ -593,7 +797,7 @@ fn get_values_types(field: &svd::Field) -> Result<Vec<EnumeratedValueType>> {
     let mut result = Vec::new();
     for enum_values in &field.enumerated_values {
         if enum_values.derived_from.is_some() {
-            return Err(ParseError::Unsupported(format!(
+            return Err(ParseErrorKind::Unsupported(format!(
             "Derived from is not supported in enumerated values. Bitfield: {} shall not have derived_from tag",
             field.name
             ))
@@ -604,7 +808,7 @@ fn get_values_types(field: &svd::Field) -> Result<Vec<EnumeratedValueType>> {
         let mut values = Vec::new();
         for val_entry in &enum_values.values {
             if val_entry.name.is_empty() {
-                return Err(ParseError::InvalidField {
+                return Err(ParseErrorKind::InvalidField {
                     field_name: field.name.clone(),
                     msg: "Value of enumeration shall have a name".to_string(),
                 }
@@ -620,7 +824,7 @@ fn get_values_types(field: &svd::Field) -> Result<Vec<EnumeratedValueType>> {
             let value = if let Some(value) = val_entry.value {
                 value
             } else {
-                return Err(ParseError::Unsupported("Default value is unsupported, all value in enumeration shall have a value defined".to_string()).into());
+                return Err(ParseErrorKind::Unsupported("Default value is unsupported, all value in enumeration shall have a value defined".to_string()).into());
             };
 
             values.push(EnumeratedSingleValue {
@@ -649,7 +853,7 @@ fn get_values_types(field: &svd::Field) -> Result<Vec<EnumeratedValueType>> {
         });
     }
     if result.len() > 2 {
-        return Err(ParseError::InvalidField {
+        return Err(ParseErrorKind::InvalidField {
             field_name: field.name.clone(),
             msg: "Only up to two enumeratedValue are supported".to_string(),
         }
@@ -660,7 +864,7 @@ fn get_values_types(field: &svd::Field) -> Result<Vec<EnumeratedValueType>> {
         .any(|f| f.usage == EnumeratedValueUsage::ReadWrite)
         && result.len() == 2
     {
-        return Err(ParseError::InvalidField {
+        return Err(ParseErrorKind::InvalidField {
             field_name: field.name.clone(),
             msg: "If two enumeratedValue are defined, one shall be read and the other write."
                 .to_string(),
@@ -668,7 +872,7 @@ fn get_values_types(field: &svd::Field) -> Result<Vec<EnumeratedValueType>> {
         .into());
     }
     if result.len() == 2 && result[0].usage == result[1].usage {
-        return Err(ParseError::InvalidField {
+        return Err(ParseErrorKind::InvalidField {
             field_name: field.name.clone(),
             msg: "If two enumeratedValue are defined, one shall be read and the other write."
                 .to_string(),
@@ -691,6 +895,11 @@ pub(super) fn parse_xml(
     xml: &mut str,
     svd_validation_level: SvdValidationLevel,
 ) -> Result<svd::Device> {
+    svd_parser::parse_with_config(xml, &parser_config(svd_validation_level))
+        .map_err(|err| svd_parser_error(err, xml))
+}
+
+fn parser_config(svd_validation_level: SvdValidationLevel) -> svd_parser::Config {
     let mut parser_config = svd_parser::Config::default();
     parser_config.expand_properties = true;
     parser_config.ignore_enums = false;
@@ -699,13 +908,31 @@ pub(super) fn parse_xml(
         SvdValidationLevel::Weak => svd::ValidateLevel::Weak,
         SvdValidationLevel::Strict => svd::ValidateLevel::Strict,
     };
-    let result = svd_parser::parse_with_config(xml, &parser_config);
-    if let Err(err) = &result {
-        if let Some(error_at) = err.downcast_ref::<svd_parser::SVDErrorAt>() {
-            error!("Error while parsing {}", error_at);
-        }
-    }
-    result
+    parser_config
+}
+
+/// Convert error of svd-parser to a [`ParseError`] with path of the item.
+///
+/// svd-parser reports the names of the item and its parents as context of the error.
+/// The item is searched in the XML to get the complete path including clusters.
+/// The position is added later by [`add_source_location`] from the node of the item.
+fn svd_parser_error(err: anyhow::Error, xml: &str) -> anyhow::Error {
+    let names: Vec<String> = err
+        .chain()
+        .filter_map(|context| {
+            let context = context.to_string();
+            let captures = regex!(r"^(?:In|Parsing) (\w+) `(.*)`").captures(&context)?;
+            (&captures[1] != "device").then(|| captures[2].to_owned())
+        })
+        .collect();
+    let Some(svd_path) = source_location::item_path(xml, &names) else {
+        return err;
+    };
+    ParseError::new(
+        ParseErrorKind::InvalidSvd(err.root_cause().to_string()),
+        svd_path,
+    )
+    .into()
 }
 
 /// Generate interrupt table including holes that will be used to create required function for cortex-m-rt
@@ -744,11 +971,76 @@ fn get_interrupt_table(
     }
 }
 
+/// Link interrupts to the service request node (SRC register) of Aurix devices.
+///
+/// SVD doesn't describe the relation between an interrupt and its SRC register.
+/// The SRC register is found by name: all registers of the `SRC` peripheral are considered and
+/// the interrupt is linked to the register whose path (cluster and register names including array indices)
+/// or register name is equal to the interrupt name. Comparison ignores case, `_` and `SRC` prefix.
+fn assign_service_request_nodes(
+    interrupt_table: &mut [Option<Interrupt>],
+    register_addresses: &IndexMap<u64, Vec<Vec<PathChunk>>>,
+) {
+    fn normalize(name: &str) -> String {
+        let name: String = name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        name.strip_prefix("src").map(str::to_owned).unwrap_or(name)
+    }
+    fn render_chunk(chunk: &PathChunk) -> String {
+        chunk.path.clone() + &chunk.index.map_or_else(String::new, |i| i.to_string())
+    }
+    let mut src_registers: HashMap<String, u64> = HashMap::new();
+    for (address, paths) in register_addresses {
+        for path in paths {
+            match path.split_first() {
+                Some((peripheral, chunks))
+                    if peripheral.path.eq_ignore_ascii_case("SRC") && !chunks.is_empty() =>
+                {
+                    let full_name: String = chunks.iter().map(render_chunk).collect();
+                    src_registers
+                        .entry(normalize(&full_name))
+                        .or_insert(*address);
+                    let register_name = render_chunk(chunks.last().unwrap());
+                    src_registers
+                        .entry(normalize(&register_name))
+                        .or_insert(*address);
+                }
+                _ => continue,
+            }
+        }
+    }
+    for interrupt in interrupt_table.iter_mut().flatten() {
+        interrupt.service_request_node = src_registers.get(&normalize(&interrupt.name)).copied();
+    }
+}
+
+/// Convert SVD device to [`IR`].
+///
+/// Errors of all items are collected and returned together as [`ParseErrors`].
 pub(super) fn svd_device2ir(
     svd_device: &svd::Device,
     custom_license_text: &Option<String>,
+    alias_registers: bool,
 ) -> Result<IR> {
-    let entity_db = get_entity_db(svd_device);
+    let (ir, errors) = svd_device2ir_keep_going(svd_device, custom_license_text, alias_registers)?;
+    if errors.is_empty() {
+        Ok(ir)
+    } else {
+        Err(ParseErrors(errors).into())
+    }
+}
+
+/// Convert SVD device to [`IR`] omitting the items with errors.
+///
+/// Return the [`IR`] of the other items and the errors of omitted items.
+pub(super) fn svd_device2ir_keep_going(
+    svd_device: &svd::Device,
+    custom_license_text: &Option<String>,
+    alias_registers: bool,
+) -> Result<(IR, Vec<ParseError>)> {
     // Use custom license if available otherwise use license in svd and if it not present use empty string.
     let license_text = custom_license_text.as_ref().map_or_else(
         || {
@@ -762,13 +1054,18 @@ pub(super) fn svd_device2ir(
         },
         |file_license| file_license.clone(),
     );
-    let mut visitor = Visitor::default();
+    let mut visitor = Visitor {
+        alias_registers,
+        ..Default::default()
+    };
     visitor.visit_device(svd_device)?;
     let device = visitor.device;
-    let interrupt_table = get_interrupt_table(&device.peripheral_mod);
-    Ok(IR {
+    let register_addresses = get_register_addresses(&device);
+    let mut interrupt_table = get_interrupt_table(&device.peripheral_mod);
+    assign_service_request_nodes(&mut interrupt_table, &register_addresses);
+    let ir = IR {
         device,
-        register_addresses: entity_db.register_addresses,
+        register_addresses,
         license_text,
         version: svd_device.version.clone(),
         interrupt_table,
@@ -776,5 +1073,6 @@ pub(super) fn svd_device2ir(
         vendor_systick_config: svd_device.cpu.as_ref().map(|x| x.has_vendor_systick),
         fpu_present: svd_device.cpu.as_ref().map(|x| x.fpu_present),
         mpu_present: svd_device.cpu.as_ref().map(|x| x.mpu_present),
-    })
+    };
+    Ok((ir, visitor.errors))
 }
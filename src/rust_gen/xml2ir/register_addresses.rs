@@ -0,0 +1,129 @@
+use super::super::ir::{Cluster, Device, PathChunk, PeripheralMod, Register};
+use super::super::util::*;
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Flat map of absolute addresses to paths of registers at that address.
+pub(super) type RegisterAddresses = IndexMap<u64, Vec<Vec<PathChunk>>>;
+
+/// Build [`RegisterAddresses`] of all registers in the [`Device`].
+///
+/// The map is built from the IR so that items omitted because of errors are not included.
+/// Primary registers come first followed by alternate registers, i.e. registers with
+/// `alternateRegister` or `alternateGroup` and registers of peripherals with
+/// `alternatePeripheral`. Both are in SVD order.
+pub(super) fn get_register_addresses(device: &Device) -> RegisterAddresses {
+    let mut register_addresses = RegisterAddresses::new();
+    for alternate in [false, true] {
+        for peripheral in device.peripheral_mod.values() {
+            let peripheral = peripheral.borrow();
+            collect_peripheral(&mut register_addresses, &peripheral, alternate);
+        }
+    }
+    register_addresses
+}
+
+/// Collect registers of all instances of a peripheral.
+fn collect_peripheral(
+    register_addresses: &mut RegisterAddresses,
+    peripheral: &PeripheralMod,
+    alternate: bool,
+) {
+    let path = peripheral.name.to_sanitized_const_ident();
+    let is_array = peripheral.base_addr.len() > 1;
+    for (index, base_addr) in peripheral.base_addr.iter().enumerate() {
+        let prefix = vec![PathChunk {
+            path: path.clone(),
+            index: is_array.then_some(index as u32),
+        }];
+        collect_registers(
+            register_addresses,
+            &prefix,
+            &peripheral.registers,
+            *base_addr,
+            peripheral.is_alternate,
+            alternate,
+        );
+        for cluster in peripheral.clusters.values() {
+            collect_cluster(
+                register_addresses,
+                &prefix,
+                &cluster.borrow(),
+                *base_addr,
+                peripheral.is_alternate,
+                alternate,
+            );
+        }
+    }
+}
+
+/// Collect registers of all instances of a cluster.
+/// Recursion point.
+fn collect_cluster(
+    register_addresses: &mut RegisterAddresses,
+    prefix: &[PathChunk],
+    cluster: &Cluster,
+    base_addr: u64,
+    is_alternate_peripheral: bool,
+    alternate: bool,
+) {
+    let path = cluster.name.to_sanitized_func_ident();
+    for index in 0..cluster.dim {
+        let mut prefix = prefix.to_owned();
+        prefix.push(PathChunk {
+            path: path.clone(),
+            index: (cluster.dim > 1).then_some(index),
+        });
+        // `cluster.offset` is relative to the containing element.
+        // See SVD spec: https://www.keil.com/pack/doc/CMSIS/SVD/html/elem_registers.html#elem_cluster
+        let base_addr = base_addr + (cluster.offset + index * cluster.dim_increment) as u64;
+        collect_registers(
+            register_addresses,
+            &prefix,
+            &cluster.registers,
+            base_addr,
+            is_alternate_peripheral,
+            alternate,
+        );
+        for child in cluster.clusters.values() {
+            collect_cluster(
+                register_addresses,
+                &prefix,
+                &child.borrow(),
+                base_addr,
+                is_alternate_peripheral,
+                alternate,
+            );
+        }
+    }
+}
+
+/// Collect all instances of registers in a peripheral or cluster.
+fn collect_registers(
+    register_addresses: &mut RegisterAddresses,
+    prefix: &[PathChunk],
+    registers: &IndexMap<String, Rc<RefCell<Register>>>,
+    base_addr: u64,
+    is_alternate_peripheral: bool,
+    alternate: bool,
+) {
+    for register in registers.values() {
+        let register = register.borrow();
+        if (is_alternate_peripheral || register.is_alternate) != alternate {
+            continue;
+        }
+        let path = register.name.to_sanitized_func_ident();
+        for index in 0..register.dim {
+            let mut key = prefix.to_owned();
+            key.push(PathChunk {
+                path: path.clone(),
+                index: (register.dim > 1).then_some(index),
+            });
+            register_addresses
+                .entry(base_addr + (register.offset + index * register.dim_increment) as u64)
+                .or_default()
+                .push(key);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Location of SVD items in the XML file used to report errors.
+use crate::svd_util::{ParseError, ParseErrors};
+use roxmltree::{Document, Node};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Tags of SVD items that have a name
+const ITEM_TAGS: &[&str] = &[
+    "peripheral",
+    "cluster",
+    "register",
+    "field",
+    "enumeratedValue",
+    "interrupt",
+];
+/// Tags that contain a list of SVD items
+const CONTAINER_TAGS: &[&str] = &["peripherals", "registers", "fields", "enumeratedValues"];
+/// Maximum number of lines between start tag of an item and its name shown in snippet
+const MAX_SNIPPET_LINES: u32 = 4;
+
+/// Add location and XML snippet of the SVD items to [`ParseError`] and [`ParseErrors`].
+///
+/// Other errors are returned unchanged.
+pub(in crate::rust_gen) fn add_source_location(
+    err: anyhow::Error,
+    xml_path: &Path,
+    xml: &str,
+) -> anyhow::Error {
+    let err = match err.downcast::<ParseError>() {
+        Ok(mut parse_error) => {
+            locate_errors(std::slice::from_mut(&mut parse_error), xml_path, xml);
+            return parse_error.into();
+        }
+        Err(err) => err,
+    };
+    match err.downcast::<ParseErrors>() {
+        Ok(mut parse_errors) => {
+            locate_errors(&mut parse_errors.0, xml_path, xml);
+            parse_errors.into()
+        }
+        Err(err) => err,
+    }
+}
+
+/// Add location and XML snippet of the SVD items to `parse_errors`
+pub(in crate::rust_gen) fn locate_errors(
+    parse_errors: &mut [ParseError],
+    xml_path: &Path,
+    xml: &str,
+) {
+    let xml = xml.trim_start_matches('\u{FEFF}');
+    let Ok(document) = Document::parse(xml) else {
+        return;
+    };
+    let lines: Vec<&str> = xml.lines().collect();
+    let device = document
+        .root()
+        .children()
+        .find(|node| node.has_tag_name("device"));
+    for parse_error in parse_errors {
+        locate(parse_error, &document, device, &lines, xml_path);
+    }
+}
+
+fn locate(
+    parse_error: &mut ParseError,
+    document: &Document,
+    device: Option<Node>,
+    lines: &[&str],
+    xml_path: &Path,
+) {
+    let item = device.and_then(|device| find_item(device, &parse_error.svd_path));
+    // Item is shown starting from its tag until its name. The name is highlighted
+    let (start, highlight) = match (parse_error.position, item) {
+        (Some(position), _) => (position, position),
+        (None, Some(item)) => {
+            let start = document.text_pos_at(item.range().start);
+            let start = (start.row, start.col);
+            let highlight = name_node(item).map_or(start, |name| {
+                let position = document.text_pos_at(name.range().start);
+                (position.row, position.col)
+            });
+            (start, highlight)
+        }
+        (None, None) => return,
+    };
+    parse_error.position = Some(start);
+    parse_error.snippet = Some(render_snippet(
+        lines,
+        xml_path,
+        start,
+        highlight,
+        &parse_error.svd_path,
+    ));
+}
+
+/// Render location in compiler diagnostic style:
+///
+/// ```text
+///   --> device.svd:27:9
+///    |
+/// 27 |         <register>
+/// 28 |           <name>CR</name>
+///    |           ^^^^^^^^^^^^^^^ TIMER.CR
+/// ```
+fn render_snippet(
+    lines: &[&str],
+    xml_path: &Path,
+    (start_line, start_column): (u32, u32),
+    (highlight_line, highlight_column): (u32, u32),
+    svd_path: &str,
+) -> String {
+    let first_line = if highlight_line - start_line < MAX_SNIPPET_LINES {
+        start_line
+    } else {
+        highlight_line
+    };
+    let gutter = highlight_line.to_string().len();
+    let mut snippet = String::new();
+    let _ = writeln!(
+        snippet,
+        "{:gutter$}--> {}:{start_line}:{start_column}",
+        "",
+        xml_path.display()
+    );
+    let _ = writeln!(snippet, "{:gutter$} |", "");
+    for line_number in first_line..=highlight_line {
+        let line = lines
+            .get(line_number as usize - 1)
+            .copied()
+            .unwrap_or_default();
+        let _ = writeln!(snippet, "{line_number:>gutter$} | {line}");
+    }
+    let highlighted_line = lines
+        .get(highlight_line as usize - 1)
+        .copied()
+        .unwrap_or_default();
+    let indent = highlight_column as usize - 1;
+    let highlighted_len = highlighted_line
+        .get(indent..)
+        .map_or(1, |text| text.trim_end().chars().count().max(1));
+    let _ = write!(
+        snippet,
+        "{:gutter$} | {:indent$}{} {svd_path}",
+        "",
+        "",
+        "^".repeat(highlighted_len)
+    );
+    snippet
+}
+
+/// Path of the SVD item named by `names`, the names of the item and its parents as reported by svd-parser.
+///
+/// svd-parser doesn't report clusters so they are searched in the XML and added to the path.
+pub(in crate::rust_gen) fn item_path(xml: &str, names: &[String]) -> Option<String> {
+    let document = Document::parse(xml.trim_start_matches('\u{FEFF}')).ok()?;
+    let device = document
+        .root()
+        .children()
+        .find(|node| node.has_tag_name("device"))?;
+    let mut svd_path = Vec::new();
+    names.iter().try_fold(device, |node, name| {
+        find_through_clusters(node, name, &mut svd_path)
+    })?;
+    Some(svd_path.join("."))
+}
+
+/// Find child item `name` of `node` also in nested clusters and add the names of the clusters and the item to `svd_path`
+fn find_through_clusters<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &str,
+    svd_path: &mut Vec<String>,
+) -> Option<Node<'a, 'input>> {
+    if let Some(item) = child_items(node).find(|item| item_name(*item) == Some(name)) {
+        svd_path.push(name.to_owned());
+        return Some(item);
+    }
+    child_items(node)
+        .filter(|item| item.has_tag_name("cluster"))
+        .find_map(|cluster| {
+            svd_path.push(item_name(cluster)?.to_owned());
+            let item = find_through_clusters(cluster, name, svd_path);
+            if item.is_none() {
+                svd_path.pop();
+            }
+            item
+        })
+}
+
+/// Find the node of an SVD item by its path. Names of arrays match the name of their elements.
+fn find_item<'a, 'input>(device: Node<'a, 'input>, svd_path: &str) -> Option<Node<'a, 'input>> {
+    if svd_path.is_empty() {
+        return None;
+    }
+    svd_path.split('.').try_fold(device, |node, expanded_name| {
+        child_items(node).find(|item| {
+            name_node(*item)
+                .and_then(|name| name.text())
+                .is_some_and(|name| name_matches(name.trim(), expanded_name))
+        })
+    })
+}
+
+/// SVD items that are children of `node` directly or in a container tag
+fn child_items<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .flat_map(|child| {
+            if CONTAINER_TAGS.contains(&child.tag_name().name()) {
+                child.children().collect()
+            } else {
+                vec![child]
+            }
+        })
+        .filter(|child| ITEM_TAGS.contains(&child.tag_name().name()))
+}
+
+fn item_name<'a>(item: Node<'a, '_>) -> Option<&'a str> {
+    name_node(item).and_then(|name| name.text()).map(str::trim)
+}
+
+fn name_node<'a, 'input>(item: Node<'a, 'input>) -> Option<Node<'a, 'input>> {
+    item.children().find(|child| child.has_tag_name("name"))
+}
+
+/// Check if `expanded_name` is `name` or an element of array `name`
+pub(in crate::rust_gen) fn name_matches(name: &str, expanded_name: &str) -> bool {
+    match name.replace("[%s]", "%s").split_once("%s") {
+        Some((prefix, suffix)) => {
+            expanded_name.len() >= prefix.len() + suffix.len()
+                && expanded_name.starts_with(prefix)
+                && expanded_name.ends_with(suffix)
+        }
+        None => name == expanded_name,
+    }
+}
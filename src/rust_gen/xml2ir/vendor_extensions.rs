@@ -0,0 +1,151 @@
+//! Parsing of `<vendorExtensions>` element of SVD files.
+//!
+//! Each child element of `<vendorExtensions>` is passed to the [`VendorExtensionHandler`]
+//! registered for its tag name. Tag names are compared without namespace prefix and
+//! elements without handler are ignored.
+use super::parser_config;
+use crate::SvdValidationLevel;
+use crate::svd_util::{ParseError, ParseErrorKind};
+use anyhow::Result;
+use log::warn;
+use roxmltree::{Document, Node};
+use svd_parser::{Parse, svd};
+
+/// Content of the supported vendor extensions
+#[derive(Debug, Default)]
+pub(in crate::rust_gen) struct VendorExtensions {
+    /// Aurix core special function registers. Peripherals are accessed with `mfcr` and `mtcr` instructions.
+    pub aurix_csfr: Vec<svd::Peripheral>,
+}
+
+/// Parser of one kind of element in `<vendorExtensions>`
+trait VendorExtensionHandler {
+    /// Tag name of the element without namespace prefix
+    fn tag_name(&self) -> &'static str;
+    /// Parse `element` and add its content to `extensions`
+    fn parse(
+        &self,
+        document: &Document,
+        element: Node,
+        config: &svd_parser::Config,
+        extensions: &mut VendorExtensions,
+    ) -> Result<()>;
+}
+
+/// Handlers of all supported vendor extensions
+const HANDLERS: &[&dyn VendorExtensionHandler] = &[&AurixCsfrHandler];
+
+/// Parse the `<vendorExtensions>` element of `xml`.
+///
+/// Returns empty [`VendorExtensions`] if the element doesn't exist.
+pub(in crate::rust_gen) fn parse_vendor_extensions(
+    xml: &str,
+    svd_validation_level: SvdValidationLevel,
+) -> Result<VendorExtensions> {
+    let document = Document::parse(xml.trim_start_matches('\u{FEFF}'))?;
+    let config = parser_config(svd_validation_level);
+    let mut extensions = VendorExtensions::default();
+    let Some(vendor_extensions) = document
+        .root_element()
+        .children()
+        .find(|node| node.has_tag_name("vendorExtensions"))
+    else {
+        return Ok(extensions);
+    };
+    for element in vendor_extensions.children().filter(Node::is_element) {
+        let tag_name = element.tag_name().name();
+        match HANDLERS
+            .iter()
+            .find(|handler| handler.tag_name() == tag_name)
+        {
+            Some(handler) => handler.parse(&document, element, &config, &mut extensions)?,
+            None => warn!("Vendor extension <{tag_name}> is not supported and is ignored"),
+        }
+    }
+    Ok(extensions)
+}
+
+/// `<aurixCSFR>` contains `<peripheral>` elements with the same format as `<peripherals>` of the device
+struct AurixCsfrHandler;
+
+impl VendorExtensionHandler for AurixCsfrHandler {
+    fn tag_name(&self) -> &'static str {
+        "aurixCSFR"
+    }
+
+    fn parse(
+        &self,
+        document: &Document,
+        element: Node,
+        config: &svd_parser::Config,
+        extensions: &mut VendorExtensions,
+    ) -> Result<()> {
+        for child in element.children().filter(Node::is_element) {
+            if !child.has_tag_name("peripheral") {
+                return Err(element_error(
+                    document,
+                    child,
+                    self.tag_name(),
+                    format!(
+                        "unexpected element <{}>, only <peripheral> is allowed",
+                        child.tag_name().name()
+                    ),
+                ));
+            }
+            let peripheral = svd::Peripheral::parse(&child, config).map_err(|err| {
+                let (node, svd_path) = innermost_invalid_item(child, config, item_name(child));
+                element_error(document, node, &svd_path, err.to_string())
+            })?;
+            extensions.aurix_csfr.push(peripheral);
+        }
+        Ok(())
+    }
+}
+
+/// Error located at the start tag of `node`
+fn element_error(
+    document: &Document,
+    node: Node,
+    svd_path: &str,
+    message: String,
+) -> anyhow::Error {
+    let position = document.text_pos_at(node.range().start);
+    let mut parse_error = ParseError::new(ParseErrorKind::InvalidSvd(message), svd_path.to_owned());
+    parse_error.position = Some((position.row, position.col));
+    parse_error.into()
+}
+
+/// Find the innermost cluster, register or field of `item` that cannot be parsed.
+///
+/// svd-parser doesn't expose the node of an error, so children are parsed one by one.
+fn innermost_invalid_item<'a, 'input>(
+    item: Node<'a, 'input>,
+    config: &svd_parser::Config,
+    svd_path: String,
+) -> (Node<'a, 'input>, String) {
+    let invalid_child = item
+        .children()
+        .filter(|child| child.has_tag_name("registers") || child.has_tag_name("fields"))
+        .chain(std::iter::once(item))
+        .flat_map(|container| container.children())
+        .find(|child| match child.tag_name().name() {
+            "cluster" => svd::Cluster::parse(child, config).is_err(),
+            "register" => svd::Register::parse(child, config).is_err(),
+            "field" => svd::Field::parse(child, config).is_err(),
+            _ => false,
+        });
+    match invalid_child {
+        Some(child) => {
+            let child_path = format!("{svd_path}.{}", item_name(child));
+            innermost_invalid_item(child, config, child_path)
+        }
+        None => (item, svd_path),
+    }
+}
+
+fn item_name(item: Node) -> String {
+    item.children()
+        .find(|child| child.has_tag_name("name"))
+        .and_then(|name| name.text())
+        .map_or_else(|| "<unknown>".to_owned(), |name| name.trim().to_owned())
+}
@@ -1,7 +1,8 @@
+use std::fmt;
 use svd_parser::svd::{self};
 
 #[derive(thiserror::Error, Debug)]
-pub enum ParseError {
+pub enum ParseErrorKind {
     #[error("Invalid peripheral {peripheral_name:?}: {msg:?}")]
     InvalidPeripheral {
         peripheral_name: String,
@@ -15,6 +16,58 @@ pub enum ParseError {
     InvalidField { field_name: String, msg: String },
     #[error("Unsupported feature {0}")]
     Unsupported(String),
+    #[error("Invalid SVD: {0}")]
+    InvalidSvd(String),
+}
+
+/// Error in an item of the SVD file
+#[derive(thiserror::Error, Debug)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// SVD path of the item, e.g. `PERIPHERAL.CLUSTER.REGISTER.FIELD`. Empty if the item is not known.
+    pub svd_path: String,
+    /// Line and column of the item in the SVD file
+    pub position: Option<(u32, u32)>,
+    /// Location and XML of the item rendered in compiler diagnostic style
+    pub snippet: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, svd_path: String) -> Self {
+        ParseError {
+            kind,
+            svd_path,
+            position: None,
+            snippet: None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.snippet {
+            Some(snippet) => write!(f, "{}\n{snippet}", self.kind),
+            None if !self.svd_path.is_empty() => write!(f, "{} in {}", self.kind, self.svd_path),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+/// Errors of all SVD items that cannot be converted
+#[derive(thiserror::Error, Debug)]
+pub struct ParseErrors(pub Vec<ParseError>);
+
+impl fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let [error] = self.0.as_slice() {
+            return write!(f, "{error}");
+        }
+        write!(f, "{} errors in SVD file", self.0.len())?;
+        for error in &self.0 {
+            write!(f, "\n{error}")?;
+        }
+        Ok(())
+    }
 }
 
 pub(crate) trait ExpandedName: svd_parser::svd::Name {
@@ -25,16 +78,16 @@ pub(crate) trait ExpandedName: svd_parser::svd::Name {
     /// This is not supported by svdconv.exe. svdconv.exe want to have name of register after array unrolling.
     /// This function shall return the name of first element after array unrolling
     /// If the element is not an array it return just a clone of the name.
-    fn get_expanded_name(&self) -> Result<String, ParseError>;
+    fn get_expanded_name(&self) -> Result<String, ParseErrorKind>;
 }
 
 impl ExpandedName for svd::Cluster {
-    fn get_expanded_name(&self) -> Result<String, ParseError> {
+    fn get_expanded_name(&self) -> Result<String, ParseErrorKind> {
         match self {
             svd::MaybeArray::Single(info) => Ok(info.name.clone()),
             svd::MaybeArray::Array(info, dim_info) => Ok(svd::cluster::expand(info, dim_info)
                 .next()
-                .ok_or(ParseError::InvalidCluster {
+                .ok_or(ParseErrorKind::InvalidCluster {
                     cluster_name: self.name.clone(),
                     msg: "Array of size 0 is not allowed".to_string(),
                 })?
@@ -45,12 +98,12 @@ impl ExpandedName for svd::Cluster {
 }
 
 impl ExpandedName for svd::Register {
-    fn get_expanded_name(&self) -> Result<String, ParseError> {
+    fn get_expanded_name(&self) -> Result<String, ParseErrorKind> {
         match self {
             svd::MaybeArray::Single(info) => Ok(info.name.clone()),
             svd::MaybeArray::Array(info, dim_info) => Ok(svd::register::expand(info, dim_info)
                 .next()
-                .ok_or(ParseError::InvalidRegister {
+                .ok_or(ParseErrorKind::InvalidRegister {
                     register_name: self.name.clone(),
                     msg: "Array of size 0 is not allowed".to_string(),
                 })?
@@ -61,12 +114,12 @@ impl ExpandedName for svd::Register {
 }
 
 impl ExpandedName for svd::Peripheral {
-    fn get_expanded_name(&self) -> Result<String, ParseError> {
+    fn get_expanded_name(&self) -> Result<String, ParseErrorKind> {
         match self {
             svd::MaybeArray::Single(info) => Ok(info.name.clone()),
             svd::MaybeArray::Array(info, dim_info) => Ok(svd::peripheral::expand(info, dim_info)
                 .next()
-                .ok_or(ParseError::InvalidPeripheral {
+                .ok_or(ParseErrorKind::InvalidPeripheral {
                     peripheral_name: self.name.clone(),
                     msg: "Array of size 0 is not allowed".to_string(),
                 })?
@@ -76,6 +129,22 @@ impl ExpandedName for svd::Peripheral {
     }
 }
 
+impl ExpandedName for svd::Field {
+    fn get_expanded_name(&self) -> Result<String, ParseErrorKind> {
+        match self {
+            svd::MaybeArray::Single(info) => Ok(info.name.clone()),
+            svd::MaybeArray::Array(info, dim_info) => Ok(svd::field::expand(info, dim_info)
+                .next()
+                .ok_or(ParseErrorKind::InvalidField {
+                    field_name: self.name.clone(),
+                    msg: "Array of size 0 is not allowed".to_string(),
+                })?
+                .name
+                .to_string()),
+        }
+    }
+}
+
 /// Trait to ger headerStructName field
 pub(crate) trait HeaderStructName {
     fn header_struct_name(&self) -> Option<String>;
@@ -98,3 +167,60 @@ impl HeaderStructName for svd::Register {
         None
     }
 }
+
+/// Create a normalized copy of `device` that can be consumed by tools without support for
+/// `derivedFrom` and arrays.
+/// Arrays are expanded, `derivedFrom` references are resolved and register properties are
+/// propagated to all registers. Each interrupt is listed once.
+/// Interrupts and enumerated values are sorted by value and
+/// bit ranges are described by `bitOffset` and `bitWidth`.
+pub(crate) fn normalize_device(device: &svd::Device) -> anyhow::Result<svd::Device> {
+    let mut device = svd_parser::expand::expand(device)?;
+    svd_parser::expand::expand_properties(&mut device);
+    // Expansion copies interrupts to all elements of a peripheral array, keep only the first one
+    let mut interrupts = std::collections::HashSet::new();
+    for peripheral in device.peripherals.iter_mut() {
+        peripheral
+            .interrupt
+            .retain(|interrupt| interrupts.insert((interrupt.name.clone(), interrupt.value)));
+        peripheral
+            .interrupt
+            .sort_by_key(|interrupt| interrupt.value);
+        if let Some(registers) = peripheral.registers.as_mut() {
+            normalize_register_clusters(registers);
+        }
+    }
+    Ok(device)
+}
+
+fn normalize_register_clusters(register_clusters: &mut [svd::RegisterCluster]) {
+    for register_cluster in register_clusters {
+        match register_cluster {
+            svd::RegisterCluster::Cluster(cluster) => {
+                normalize_register_clusters(&mut cluster.children)
+            }
+            svd::RegisterCluster::Register(register) => {
+                for field in register.fields.iter_mut().flatten() {
+                    field.bit_range.range_type = svd::BitRangeType::OffsetWidth;
+                    for enumerated_values in field.enumerated_values.iter_mut() {
+                        // Default value has no value and is placed last
+                        enumerated_values
+                            .values
+                            .sort_by_key(|value| (value.value.is_none(), value.value));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Encode `device` as SVD with peripherals, registers and fields ordered by address
+pub(crate) fn encode_device(device: &svd::Device) -> anyhow::Result<String> {
+    use svd_encoder::{DerivableSorting, RcSorting, Sorting};
+    let by_offset = DerivableSorting::Unchanged(Some(Sorting::Offset));
+    let config = svd_encoder::Config::default()
+        .peripheral_sorting(by_offset)
+        .register_cluster_sorting(RcSorting::Unchanged(by_offset))
+        .field_sorting(by_offset);
+    Ok(svd_encoder::encode_with_config(device, &config)?)
+}
@@ -0,0 +1,29 @@
+/* Check layout of structs and values of macros of header generated from test_svd/simple.xml */
+#include <stddef.h>
+#include "Test_pac.h"
+
+_Static_assert(offsetof(TIMER_Type, SR) == 0x4, "TIMER.SR");
+_Static_assert(offsetof(TIMER_Type, INT) == 0x10, "TIMER.INT");
+_Static_assert(offsetof(TIMER_Type, ARRAYREG[1]) == 0x54, "TIMER.ARRAYREG[1]");
+_Static_assert(offsetof(TIMER_Type, register64bit) == 0x60, "TIMER.register64bit");
+_Static_assert(offsetof(TIMER_Type, Cluster1.Cluster1.NestedReg) == 0x200, "TIMER.Cluster1.Cluster1");
+_Static_assert(offsetof(TIMER_Type, ClusterDim[1].CR) == 0x1100, "TIMER.ClusterDim[1]");
+/* Register with same name of peripheral is renamed to not clash with peripheral macro */
+_Static_assert(offsetof(TIMER_Type, TIMER_) == 0x2000, "TIMER.TIMER");
+_Static_assert(offsetof(UART_Type, RegEnumValue) == 0x200, "UART.RegEnumValue");
+_Static_assert(sizeof(UART_Type) == 0x1004, "UART");
+_Static_assert(offsetof(DimIndexPeri_Type, Clust[2].CReg[1]) == 0x7c, "DimIndexPeri.Clust[2].CReg[1]");
+_Static_assert(offsetof(GPIO_Type, IFLAG) == 0x10, "GPIO.IFLAG");
+
+_Static_assert(TIMER_BASE == 0x40010000UL, "TIMER_BASE");
+_Static_assert(TIMER_SR_RELOAD_Msk == 0xc000UL, "TIMER_SR_RELOAD_Msk");
+_Static_assert(TIMER_BITFIELD_REG_FieldArray7_Pos == 30UL, "TIMER_BITFIELD_REG_FieldArray7_Pos");
+_Static_assert(TIMER_INT_MODE_Overflow == 2UL, "TIMER_INT_MODE_Overflow");
+/* Derived register has the bitfields of its base register */
+_Static_assert(DerivedTest_DerivedRegister_RUN_Msk == 0x1UL, "DerivedTest_DerivedRegister_RUN_Msk");
+_Static_assert(SysTick_IRQn == -1, "SysTick_IRQn");
+_Static_assert(INT_FOO_IRQn == 3, "INT_FOO_IRQn");
+
+int main(void) {
+    return TIMER->SR & TIMER_SR_RUN_Msk;
+}
@@ -0,0 +1,514 @@
+use test_pac::{tracing, *};
+
+fn mfcr_fn(addr: u16) -> u32 {
+    println!("mfcr addr=0x{addr:X}");
+    0x0
+}
+fn mtcr_fn(addr: u16, val: u32) {
+    println!("mtcr addr=0x{addr:X}\tval=0x{val:X}");
+}
+
+fn main() {
+    let _ = tracing::set_mfcr_fn(mfcr_fn);
+    let _ = tracing::set_mtcr_fn(mtcr_fn);
+    unsafe {
+        let register = CSFR_CPU.biv();
+        let register_value = register.read();
+        register.write(register_value);
+        CSFR_CPU.biv().modify(|f| f);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use test_pac::{
+        reg_name::{csfr_name_from_addr, reg_name_from_addr},
+        tracing::InstallHandler,
+        *,
+    };
+
+    thread_local! {
+        static CSFR_WRITES: RefCell<Vec<(u16, u32)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn record_mtcr_fn(addr: u16, val: u32) {
+        CSFR_WRITES.with(|writes| writes.borrow_mut().push((addr, val)));
+    }
+
+    #[test]
+    fn csfr_tracing_test() {
+        test_pac::tracing::set_mfcr_fn(|addr| addr as u32 | 0x1_0000).unwrap();
+        test_pac::tracing::set_mtcr_fn(record_mtcr_fn).unwrap();
+        unsafe {
+            assert_eq!(CSFR_CPU.biv().read().get_raw(), 0x1_FE20);
+            CSFR_CPU.biv().modify(|r| r.set_raw(r.get_raw() + 1));
+            CSFR_CPU.dpr_dpry_l1().write(csfr_cpu0::DprDpRyL::new(0x8));
+        }
+        let writes = CSFR_WRITES.with(|writes| writes.borrow().clone());
+        assert_eq!(writes, [(0xFE20, 0x1_FE21), (0xC008, 0x8)]);
+    }
+
+    thread_local! {
+        static WRITES: RefCell<Vec<(usize, usize, u64, u64)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn record_ldmst_fn(addr: usize, len: usize, data: u64, mask: u64) {
+        WRITES.with(|writes| writes.borrow_mut().push((addr, len, data, mask)));
+    }
+
+    fn record_write_fn(addr: usize, len: usize, data: u64) {
+        WRITES.with(|writes| writes.borrow_mut().push((addr, len, data, 0)));
+    }
+
+    #[test]
+    fn ldmst_test() {
+        test_pac::tracing::set_ldmst_fn(record_ldmst_fn).unwrap();
+        unsafe { TIMER.bitfield_reg().modify_atomic(|r| r.bitfieldrw().set(0x5)) };
+        let writes = WRITES.with(|writes| writes.borrow().clone());
+        assert_eq!(writes, [(0x4001_0000, 4, 0x500, 0xF00)]);
+    }
+
+    /// Without ldmst handler the masked store is executed with read and write handlers
+    #[test]
+    fn ldmst_read_write_test() {
+        test_pac::tracing::set_read_fn(|_addr, _len| 0x1234_5678).unwrap();
+        test_pac::tracing::set_write_fn(record_write_fn).unwrap();
+        unsafe { TIMER.bitfield_reg().modify_atomic(|r| r.bitfieldrw().set(0x5)) };
+        let writes = WRITES.with(|writes| writes.borrow().clone());
+        assert_eq!(writes, [(0x4001_0000, 4, 0x1234_5578, 0)]);
+    }
+
+    #[test]
+    fn csfr_name_test() {
+        assert_eq!(names(csfr_name_from_addr(0xFE20).unwrap()), ["CSFR_CPU.biv()"]);
+        assert!(names(csfr_name_from_addr(0xC008).unwrap()).contains(&"CSFR_CPU.dpr_dpry_l1()"));
+        assert!(csfr_name_from_addr(0x1).is_none());
+    }
+
+    /// Names of registers separated by `,` as returned by `reg_name` module
+    fn names(names: &str) -> Vec<&str> {
+        names.split(',').map(str::trim).filter(|name| !name.is_empty()).collect()
+    }
+
+    /// Addresses of registers in clusters include the address offsets of the clusters
+    #[test]
+    fn reg_name_cluster_offset_test() {
+        let nested = TIMER.cluster1().cluster1().nestedreg().addr();
+        // Offsets 0x100 of both clusters
+        assert_eq!(nested, 0x4001_0200);
+        assert_eq!(
+            names(reg_name_from_addr(nested as u64).unwrap()),
+            ["TIMER.cluster1().cluster1().nestedreg()"]
+        );
+    }
+
+    #[test]
+    fn reg_paths_test() {
+        use test_pac::reg_name::{PathChunk, format_reg_value, reg_paths_from_addr};
+
+        // Primary register comes first even if alternate register is defined before
+        let stat = GPIO.stat().addr() as u64;
+        let paths: Vec<String> = reg_paths_from_addr(stat).iter().map(ToString::to_string).collect();
+        assert_eq!(paths, ["GPIO.stat()", "GPIO.stat_raw()"]);
+        assert!(reg_name_from_addr(stat).unwrap().trim().starts_with("GPIO.stat(),"));
+        // Metadata takes the same register as primary register
+        let (_, register) = test_pac::metadata::register_at(stat).unwrap();
+        assert_eq!(register.name, "STAT");
+        assert!(!register.alternate);
+        let names: Vec<&str> = test_pac::metadata::registers_at(stat).map(|(_, register)| register.name).collect();
+        assert_eq!(names, ["STAT_RAW", "STAT"]);
+        assert_eq!(register.format_fields(0x101, 0x100), "{ EN=0x1 }");
+
+        let path = reg_paths_from_addr(TIMER.clusterdim()[2].cr().addr() as u64)[0];
+        assert_eq!(
+            path.chunks,
+            [
+                PathChunk { name: "TIMER", index: None },
+                PathChunk { name: "clusterdim", index: Some(2) },
+                PathChunk { name: "cr", index: None },
+            ]
+        );
+        assert_eq!((path.peripheral(), path.register()), ("TIMER", "cr"));
+        assert!(reg_paths_from_addr(0x1).is_empty());
+
+        assert_eq!(
+            format_reg_value(stat, 0x101),
+            "GPIO.stat() = 0x101 { EVT=0x1, ERR=0x0, EN=0x1 }"
+        );
+        assert_eq!(format_reg_value(0x1, 0x5), "0x1 = 0x5");
+    }
+
+    #[test]
+    fn reg_location_test() {
+        use test_pac::metadata::Access;
+        use test_pac::reg_name::{PathChunk, RegisterLocation, parse_path, reg_location_from_name};
+
+        assert_eq!(
+            parse_path("UART[1].reg1_()[0]").unwrap(),
+            [
+                PathChunk { name: "UART", index: Some(1) },
+                PathChunk { name: "reg1_", index: Some(0) },
+            ]
+        );
+        assert!(parse_path("TIMER..sr()").is_err());
+        assert!(parse_path("TIMER.sr()[x]").is_err());
+
+        let sr = RegisterLocation {
+            addr: TIMER.sr().addr() as u64,
+            len: 2,
+            access: Access::R,
+        };
+        assert_eq!(reg_location_from_name("TIMER.sr()"), Some(sr));
+        assert_eq!(reg_location_from_name("timer.SR"), Some(sr));
+        assert_eq!(
+            reg_location_from_name("TIMER.r#match()").unwrap().addr,
+            TIMER.r#match().addr() as u64
+        );
+        assert_eq!(
+            reg_location_from_name("TIMER.clusterdim()[2].cr()").unwrap().addr,
+            TIMER.clusterdim()[2].cr().addr() as u64
+        );
+        assert_eq!(reg_location_from_name("TIMER.unknown()"), None);
+        // All register names of the forward lookup are found
+        for addr in [0x40010000, 0x40010058] {
+            for name in reg_name_from_addr(addr).unwrap().split(',') {
+                if !name.trim().is_empty() {
+                    assert_eq!(reg_location_from_name(name).unwrap().addr, addr);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn metadata_derived_register_test() {
+        let derived_test = test_pac::metadata::peripheral("DerivedTest").unwrap();
+        let register = |name| derived_test.register(name).unwrap();
+        // Derived registers have the bitfields of their base register
+        assert_eq!(register("DerivedRegister").fields, register("BaseRegister").fields);
+        let run = register("DerivedRegister").field("RUN").unwrap();
+        assert_eq!(run.enum_name(1), Some("RUNNING"));
+        assert!(register("DerivedFromFarAway").field("PSC").is_some());
+    }
+
+    /// Simulated registers keeping the last written value
+    #[derive(Default)]
+    struct Memory(std::collections::HashMap<usize, u64>);
+
+    impl test_pac::tracing::RegisterHandler for Memory {
+        fn read(&mut self, addr: usize, _len: usize) -> u64 {
+            self.0.get(&addr).copied().unwrap_or_default()
+        }
+        fn write(&mut self, addr: usize, _len: usize, value: u64) {
+            self.0.insert(addr, value);
+        }
+    }
+
+    #[test]
+    fn scoped_handler_test() {
+        let _guard = test_pac::tracing::set_handler(Box::new(Memory::default()));
+        unsafe {
+            TIMER.nobitfield_reg().write_raw(0x42);
+            assert_eq!(TIMER.nobitfield_reg().read().get(), 0x42);
+            {
+                // Inner handler is used until its guard is dropped
+                let _inner_guard = test_pac::tracing::set_handler(Box::new(Memory::default()));
+                assert_eq!(TIMER.nobitfield_reg().read().get(), 0x0);
+            }
+            assert_eq!(TIMER.nobitfield_reg().read().get(), 0x42);
+        }
+    }
+
+    /// Guards can be dropped in any order, the handler installed last that is not uninstalled is used
+    #[test]
+    fn handler_guard_order_test() {
+        use test_pac::register_file::RegisterFile;
+        let addr = TIMER.nobitfield_reg().addr();
+        let (first, second, third) = (RegisterFile::new(), RegisterFile::new(), RegisterFile::new());
+        let first_guard = first.install();
+        let second_guard = second.install();
+        drop(first_guard);
+        unsafe { TIMER.nobitfield_reg().write_raw(0x1) };
+        let _third_guard = third.install();
+        drop(second_guard);
+        unsafe { TIMER.nobitfield_reg().write_raw(0x2) };
+        assert_eq!((first.get(addr), second.get(addr), third.get(addr)), (0x0, 0x1, 0x2));
+    }
+
+    /// Closures can capture state shared with the test
+    #[test]
+    fn closure_fn_test() {
+        let reads = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = reads.clone();
+        test_pac::tracing::set_read_fn(move |_addr, _len| {
+            counter.set(counter.get() + 1);
+            0
+        })
+        .unwrap();
+        unsafe {
+            let _ = TIMER.sr().read();
+            let _ = TIMER.sr().read();
+        }
+        assert_eq!(reads.get(), 2);
+    }
+
+    /// Global handler is used by spawned threads
+    #[test]
+    fn global_handler_test() {
+        struct Shared(std::sync::Arc<std::sync::Mutex<Vec<(usize, u64)>>>);
+        impl test_pac::tracing::RegisterHandler for Shared {
+            fn read(&mut self, _addr: usize, _len: usize) -> u64 {
+                0
+            }
+            fn write(&mut self, addr: usize, _len: usize, value: u64) {
+                self.0.lock().unwrap().push((addr, value));
+            }
+        }
+        let writes = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let later_writes = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let guard = test_pac::tracing::set_global_handler(Box::new(Shared(writes.clone())));
+        std::thread::spawn(|| unsafe { TIMER.nobitfield_reg().write_raw(0x7) })
+            .join()
+            .unwrap();
+        // Guards can be dropped in any order
+        let later_guard = test_pac::tracing::set_global_handler(Box::new(Shared(later_writes.clone())));
+        drop(guard);
+        std::thread::spawn(|| unsafe { TIMER.nobitfield_reg().write_raw(0x8) })
+            .join()
+            .unwrap();
+        drop(later_guard);
+        // After the guards are dropped, accesses without handler panic
+        assert!(
+            std::thread::spawn(|| unsafe { TIMER.nobitfield_reg().write_raw(0x9) })
+                .join()
+                .is_err()
+        );
+        assert_eq!(*writes.lock().unwrap(), [(TIMER.nobitfield_reg().addr(), 0x7)]);
+        assert_eq!(*later_writes.lock().unwrap(), [(TIMER.nobitfield_reg().addr(), 0x8)]);
+    }
+
+    /// Simulated registers start with reset values and writes keep read-only bits
+    #[test]
+    fn register_file_test() {
+        let registers = test_pac::register_file::RegisterFile::new();
+        let _guard = registers.install();
+        unsafe {
+            assert_eq!(TIMER.register64bit().read().get_raw(), u64::MAX);
+            // Only bit 0 is writable
+            TIMER.register64bit().write_raw(0);
+            assert_eq!(registers.get(TIMER.register64bit().addr()), u64::MAX - 1);
+            // Read-only RUN bit is set by the test and kept by writes
+            registers.set(TIMER.sr().addr(), 0x1);
+            TIMER.sr().write_read_only(timer::Sr::new(0x100));
+            assert_eq!(TIMER.sr().read().get_raw(), 0x101);
+            // Derived register inherits the read-only RUN bit of its base register
+            DERIVEDTEST.derivedregister().write_raw(0xffff);
+            assert_eq!(registers.get(DERIVEDTEST.derivedregister().addr()), 0x0);
+        }
+        registers.reset();
+        assert_eq!(registers.get(TIMER.register64bit().addr()), u64::MAX);
+        assert_eq!(registers.get(TIMER.sr().addr()), 0x0);
+    }
+
+    /// Recorder checks expectations and decodes bitfields in failure messages
+    #[test]
+    fn recorder_test() {
+        use test_pac::register_file::RegisterFile;
+        use test_pac::tracing::{Expectation, Recorder};
+        let registers = RegisterFile::new();
+        let recorder = Recorder::new(registers.clone());
+        let _guard = recorder.install();
+        unsafe {
+            TIMER.bitfield_reg().modify(|r| r.bitfieldrw().set(0x5));
+            registers.set(TIMER.sr().addr(), 0x1);
+            let _ = TIMER.sr().read();
+            registers.set(TIMER.sr().addr(), 0x0);
+            let _ = TIMER.sr().read();
+        }
+        assert_eq!(recorder.accesses().len(), 4);
+        recorder.assert_ordered(&[
+            Expectation::write(TIMER.bitfield_reg()).field("BitfieldRW", 0x5),
+            Expectation::read_until(TIMER.sr()).field("RUN", 0),
+        ]);
+        recorder.assert_unordered(&[
+            Expectation::read(TIMER.sr()).field("RUN", 0),
+            Expectation::read(TIMER.sr()).field("RUN", 1),
+        ]);
+        let message = recorder
+            .check_ordered(&[Expectation::read(TIMER.sr()).field("RUN", 0)])
+            .unwrap_err();
+        assert!(
+            message.contains("Expectation 0 not met: read TIMER.SR with RUN=0x0"),
+            "{message}"
+        );
+        assert!(
+            message.contains("Found access 2: read TIMER.SR @ 0x40010004 = 0x1 { RUN=0x1 (RUNNING)"),
+            "{message}"
+        );
+        assert!(
+            recorder
+                .check_unordered(&[Expectation::write(TIMER.bitfield_reg()).value(0)])
+                .is_err()
+        );
+    }
+
+    /// Bitfields of expectations are taken from the register type, not from the first register at the address
+    #[test]
+    fn expectation_register_type_test() {
+        use test_pac::tracing::{Expectation, Recorder};
+        let recorder = Recorder::new(test_pac::register_file::RegisterFile::new());
+        let _guard = recorder.install();
+        unsafe {
+            GPIO.stat().write_raw(0x1);
+            DERIVEDTEST.derivedregister().write_raw(0x1);
+        }
+        let stat = Expectation::write(GPIO.stat()).field("EVT", 1);
+        assert_eq!(stat.to_string(), "write GPIO.STAT with EVT=0x1");
+        assert_eq!(
+            Expectation::write(GPIO.stat_raw()).value(1).to_string(),
+            "write GPIO.STAT_RAW with value=0x1"
+        );
+        recorder.assert_ordered(&[
+            stat,
+            Expectation::write(DERIVEDTEST.derivedregister()).field("RUN", 1),
+        ]);
+    }
+
+    /// Simulated bitfields behave as described in SVD and custom behaviours can be attached
+    #[test]
+    fn register_behaviour_test() {
+        let registers = test_pac::register_file::RegisterFile::new();
+        let _guard = registers.install();
+        let stat = GPIO.stat().addr();
+        unsafe {
+            // ERR is cleared by reading the register
+            registers.set(stat, 0x3);
+            assert_eq!(GPIO.stat().read().get_raw(), 0x3);
+            assert_eq!(registers.get(stat), 0x1);
+            // EVT is cleared by writing 1
+            GPIO.stat().write_raw(0x101);
+            assert_eq!(registers.get(stat), 0x100);
+        }
+        // Start bit is cleared by hardware and the status is set at the third poll
+        registers.self_clearing(TIMER.nobitfield_reg().addr(), 0x1);
+        registers.set_after_reads(TIMER.sr().addr(), 0x1, 3);
+        unsafe {
+            TIMER.nobitfield_reg().write_raw(0x3);
+            assert_eq!(TIMER.nobitfield_reg().read().get_raw(), 0x2);
+            let mut polls = 1;
+            while TIMER.sr().read().get_raw() & 0x1 == 0 {
+                polls += 1;
+            }
+            assert_eq!(polls, 3);
+        }
+    }
+
+    /// Accesses are exported to trace files and reads of the traces are replayed
+    #[test]
+    fn trace_file_test() {
+        use test_pac::register_file::RegisterFile;
+        use test_pac::trace_file::{Replay, TraceFormat, TraceWriter};
+        for (format, read_line) in [
+            (
+                TraceFormat::JsonLines,
+                r#""thread":"test::trace_file_test","kind":"read","addr":"0x40010004","len":2,"value":"0x1","mask":"0xffffffffffffffff","name":"TIMER.sr()","fields":{"RUN":"0x1","#,
+            ),
+            (
+                TraceFormat::Csv,
+                ",test::trace_file_test,read,0x40010004,2,0x1,0xffffffffffffffff,TIMER.sr(),RUN=0x1;",
+            ),
+        ] {
+            let path = std::env::temp_dir().join(format!(
+                "svd2pac_trace_{}_{format:?}",
+                std::process::id()
+            ));
+            let registers = RegisterFile::new();
+            registers.set(TIMER.sr().addr(), 0x1);
+            let writer = TraceWriter::create(registers, &path, format).unwrap();
+            {
+                let _guard = writer.install();
+                unsafe {
+                    let _ = TIMER.sr().read();
+                    TIMER.nobitfield_reg().write_raw(0x42);
+                }
+            }
+            // Lines are flushed while the writer is alive
+            let trace = std::fs::read_to_string(&path).unwrap();
+            assert!(trace.contains(read_line), "{trace}");
+
+            let replay = Replay::open(&path, format).unwrap();
+            assert_eq!(replay.remaining(), 1);
+            {
+                let _guard = replay.install();
+                unsafe {
+                    assert_eq!(TIMER.sr().read().get_raw(), 0x1);
+                    TIMER.nobitfield_reg().write_raw(0x43);
+                }
+            }
+            assert_eq!(replay.remaining(), 0);
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    /// Line breaks in CSV values are replaced to keep one access per line
+    #[test]
+    fn trace_file_line_break_test() {
+        use test_pac::register_file::RegisterFile;
+        use test_pac::trace_file::{Replay, TraceFormat, TraceWriter};
+        let path = std::env::temp_dir().join(format!(
+            "svd2pac_trace_line_break_{}",
+            std::process::id()
+        ));
+        let registers = RegisterFile::new();
+        registers.set(TIMER.sr().addr(), 0x1);
+        let writer = TraceWriter::create(registers, &path, TraceFormat::Csv).unwrap();
+        std::thread::Builder::new()
+            .name("first\nsecond".to_owned())
+            .spawn(move || {
+                let _guard = writer.install();
+                unsafe {
+                    let _ = TIMER.sr().read();
+                }
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        let trace = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(trace.lines().count(), 2, "{trace}");
+        assert!(trace.contains(",first second,read,"), "{trace}");
+        assert_eq!(Replay::open(&path, TraceFormat::Csv).unwrap().remaining(), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// Traces written by other tools are replayed
+    #[test]
+    fn replay_foreign_trace_test() {
+        use test_pac::trace_file::{Replay, TraceFormat};
+        let sr = TIMER.sr().addr();
+        // Spaces, escaped quotes, numeric addresses and other members
+        let json = format!(
+            "{{ \"thread\": \"say \\\"hi\\\"\", \"kind\": \"read\", \"addr\": {sr}, \"value\": \"0x1\", \"extra\": [1, 2] }}\n\
+             {{\"value\":2,\"addr\":\"{sr:#x}\",\"kind\":\"read\"}}\n"
+        );
+        // Reordered columns, other columns and optional columns missing
+        let csv = format!("value,comment,addr,kind\n0x1,\"a, b\",{sr:#x},read\n2,,{sr},read\n");
+        for (format, trace) in [(TraceFormat::JsonLines, json), (TraceFormat::Csv, csv)] {
+            let replay = Replay::from_reader(trace.as_bytes(), format).unwrap();
+            assert_eq!(replay.remaining(), 2);
+            let _guard = replay.install();
+            unsafe {
+                assert_eq!(TIMER.sr().read().get_raw(), 0x1);
+                assert_eq!(TIMER.sr().read().get_raw(), 0x2);
+            }
+        }
+        // Renamed required columns are reported
+        let error = Replay::from_reader("kind,address,value\n".as_bytes(), TraceFormat::Csv)
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "Missing column addr");
+        let error = Replay::from_reader("{\"kind\":\"read\",\"value\":1".as_bytes(), TraceFormat::JsonLines)
+            .err()
+            .unwrap();
+        assert!(error.to_string().starts_with("Line 1: Invalid JSON"), "{error}");
+    }
+}
@@ -47,6 +47,9 @@ mod app {
                     .bitfieldw()
                     .set(3)
             });
+            // Single bit fields can be updated atomically through bit-band alias
+            TIMER.bitfield_reg().boolrw_bb().set_atomic();
+            TIMER.bitfield_reg().boolrw_bb().clear_atomic();
         }
     }
 }
@@ -0,0 +1,43 @@
+fn main() {}
+
+#[cfg(test)]
+mod test {
+    use test_pac::register_file::RegisterFile;
+    use test_pac::tracing::{Expectation, InstallHandler, Recorder};
+    use test_pac::*;
+
+    /// Bit-band alias address is computed from the register address and the bit offset
+    #[test]
+    fn bit_band_alias_addr_test() {
+        let reg = TIMER.bitfield_reg();
+        assert_eq!(reg.addr(), 0x4001_0000);
+        // Alias of bit 2 of the register at offset 0x10000 of the bit-band region
+        assert_eq!(reg.boolrw_bb().alias_addr(), 0x4220_0008);
+        assert_eq!(
+            reg.boolrw_bb().alias_addr(),
+            0x4200_0000 + (reg.addr() - 0x4000_0000) * 32 + 2 * 4
+        );
+    }
+
+    /// Writes to the bit-band alias are traced as read/modify/write of the target register
+    #[test]
+    fn bit_band_tracing_test() {
+        let registers = RegisterFile::new();
+        let recorder = Recorder::new(registers.clone());
+        let _guard = recorder.install();
+        let reg = TIMER.bitfield_reg();
+        unsafe {
+            reg.boolrw_bb().set_atomic();
+            assert_eq!(registers.get(reg.addr()) & 0x4, 0x4);
+            reg.boolrw_bb().clear_atomic();
+            assert_eq!(registers.get(reg.addr()) & 0x4, 0x0);
+        }
+        recorder.assert_ordered(&[
+            Expectation::read(reg),
+            Expectation::write(reg).field("BoolRW", 1),
+            Expectation::read(reg),
+            Expectation::write(reg).field("BoolRW", 0),
+        ]);
+        assert_eq!(recorder.accesses().len(), 4);
+    }
+}
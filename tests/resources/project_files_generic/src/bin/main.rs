@@ -124,7 +124,20 @@ fn main() -> ! {
             .clu3st()
             .aregd()
             .modify(|f| f.arraybitfield_c().set(0x2));
+
+        // Atomic update of bits through companion SET/CLR/TGL registers
+        GPIO.out().set_bits(0x1);
+        GPIO.out().clear_fields(gpio::Out::new(0).pin1().set(true));
+        GPIO.out().toggle_fields(gpio::Out::new(0).mode().set(0x3));
     }
+
+    // Interrupt enumeration is available for all targets
+    let interrupt = Interrupt::TIMER0;
+    let _number: u16 = interrupt.number();
+    let _description: &'static str = interrupt.description();
+    let _peripheral: &'static str = interrupt.peripheral();
+    let _ = Interrupt::try_from(2).is_ok_and(|x| x == Interrupt::UARTINT);
+
     #[allow(clippy::empty_loop)]
     loop {}
 }
@@ -120,4 +120,146 @@ mod test {
         assert_regname(UART[0].reg8bitraw().addr(), "UART[0].reg8bitraw()");
         assert_regname(UART[0].regbitfieldraw().addr(), "UART[0].regbitfieldraw()");
     }
+
+    #[test]
+    fn interrupt_test() {
+        assert_eq!(Interrupt::TIMER0.number(), 0);
+        assert_eq!(Interrupt::UARTINT.number(), 2);
+        assert_eq!(Interrupt::try_from(3), Ok(Interrupt::INT_FOO));
+        assert_eq!(Interrupt::try_from(1), Err(TryFromInterruptError(1)));
+        assert_eq!(Interrupt::TIMER0.description(), "Timer 0 interrupt");
+        assert_eq!(Interrupt::TIMER0.peripheral(), "TIMER");
+        assert_eq!(Interrupt::UARTINT.peripheral(), "UART");
+    }
+
+    #[test]
+    fn debug_format_test() {
+        let value = timer::BitfieldReg::new(0b101_100)
+            .bitfieldenumerated()
+            .set(bitfield_reg::BitfieldEnumerated::GPIOA_1);
+        let formatted = format!("{value:?}");
+        assert!(formatted.starts_with("BitfieldReg { BoolR: false, BoolRW: true, BitfieldR: 0x5,"));
+        assert!(formatted.contains("BitfieldEnumerated: GPIOA_1"));
+        // Write-only fields are not printed
+        assert!(!formatted.contains("BoolW"));
+        assert_eq!(
+            format!("{:?}", bitfield_reg::BitfieldEnumerated::new(0xf)),
+            "Unknown(0xf)"
+        );
+        assert_eq!(
+            format!("{:?}", timer::NobitfieldReg::new(0x12)),
+            "NobitfieldReg(0x12)"
+        );
+    }
+
+    thread_local! {
+        static WRITES: std::cell::RefCell<Vec<(usize, u64)>> = const { std::cell::RefCell::new(Vec::new()) };
+    }
+    fn record_write_fn(addr: usize, _len: usize, val: u64) {
+        WRITES.with(|writes| writes.borrow_mut().push((addr, val)));
+    }
+
+    #[test]
+    fn alias_registers_test() {
+        test_pac::tracing::set_write_fn(record_write_fn).unwrap();
+        unsafe {
+            GPIO.out().set_bits(0x3);
+            GPIO.out().clear_fields(gpio::Out::default().pin1().set(true));
+            GPIO.out()
+                .toggle_fields(gpio::Out::new(0xff).mode().set(0x2));
+        }
+        let addr = GPIO.out().addr();
+        assert_regname(addr + 0x4, "GPIO.out_set()");
+        assert_regname(addr + 0x8, "GPIO.out_clr()");
+        assert_regname(addr + 0xC, "GPIO.port_tgl()");
+        WRITES.with(|writes| {
+            assert_eq!(
+                *writes.borrow(),
+                vec![(addr + 0x4, 0x3), (addr + 0x8, 0x2), (addr + 0xC, 0x20)]
+            )
+        });
+    }
+
+    #[test]
+    fn serde_test() {
+        let value = timer::BitfieldReg::new(0b101_100)
+            .bitfieldenumerated()
+            .set(bitfield_reg::BitfieldEnumerated::GPIOA_1);
+        let json = serde_json::to_string(&value).unwrap();
+        assert!(json.starts_with(
+            r#"{"BoolR":false,"BoolW":false,"BoolRW":true,"BitfieldR":5,"#
+        ));
+        assert!(json.contains(r#""BitfieldEnumerated":"GPIOA_1""#));
+        let parsed: timer::BitfieldReg = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.get_raw(), value.get_raw());
+
+        // Unknown enumerated values are serialized as numbers
+        assert_eq!(
+            serde_json::to_string(&bitfield_reg::BitfieldEnumerated::new(0xf)).unwrap(),
+            "15"
+        );
+        let parsed: bitfield_reg::BitfieldEnumerated = serde_json::from_str("15").unwrap();
+        assert_eq!(parsed, bitfield_reg::BitfieldEnumerated::new(0xf));
+        assert!(serde_json::from_str::<bitfield_reg::BitfieldEnumerated>(r#""GPIOB""#).is_err());
+        assert!(serde_json::from_str::<bitfield_reg::BitfieldEnumerated>("256").is_err());
+
+        // Registers without bitfields are serialized as numbers
+        assert_eq!(
+            serde_json::to_string(&timer::NobitfieldReg::new(0x12)).unwrap(),
+            "18"
+        );
+    }
+
+    #[test]
+    fn metadata_test() {
+        use test_pac::metadata::{self, Access};
+        let timer = metadata::peripheral("TIMER").unwrap();
+        let reg = timer.register("BITFIELD_REG").unwrap();
+        assert_eq!(reg.address, TIMER.bitfield_reg().addr() as u64);
+        assert_eq!(reg.size, 32);
+        assert_eq!(reg.access, Access::RW);
+        assert_eq!(reg.reset_mask, 0x1337F7F);
+
+        let field = reg.field("BitfieldEnumerated").unwrap();
+        assert_eq!((field.offset, field.width), (12, 4));
+        let value = timer::BitfieldReg::default()
+            .bitfieldenumerated()
+            .set(bitfield_reg::BitfieldEnumerated::GPIOA_1);
+        assert_eq!(
+            field.enum_name(field.extract(value.get_raw().into())),
+            Some("GPIOA_1")
+        );
+        assert!(reg.field("FieldArray[7]").is_some());
+
+        // Registers in cluster arrays are described with their absolute address
+        let cr = timer.register("ClusterDim[2].CR").unwrap();
+        assert_eq!(cr.address, TIMER.clusterdim()[2].cr().addr() as u64);
+
+        // Peripheral arrays are expanded
+        assert_eq!(
+            metadata::peripheral("UART[1]").unwrap().base_address,
+            0x5000_1004
+        );
+    }
+
+    #[test]
+    fn dump_test() {
+        test_pac::tracing::set_read_fn(|addr, _len| addr as u64 & 0xff).unwrap();
+        let mut dump = Vec::new();
+        TIMER.dump(|name, addr, value| dump.push((name, addr, value)));
+        let names: Vec<&str> = dump.iter().map(|(name, ..)| *name).collect();
+        assert!(names.contains(&"BITFIELD_REG"));
+        assert!(names.contains(&"ARRAYREG[3]"));
+        assert!(names.contains(&"ClusterDim[3].CR"));
+        assert!(names.contains(&"Cluster1.HSSL.CH[1].HSSLxCOKy"));
+        // Write-only registers are not read
+        assert!(!names.contains(&"INT"));
+        let sr_addr = TIMER.sr().addr();
+        assert!(dump.contains(&("SR", sr_addr, sr_addr as u64 & 0xff)));
+
+        let mut names = Vec::new();
+        GPIO.dump(|name, _, _| names.push(name));
+        // Registers with side effects on read are skipped
+        assert_eq!(names, ["OUT"]);
+    }
 }
@@ -0,0 +1,57 @@
+mod common;
+use common::*;
+use std::env;
+use std::fs;
+use svd2pac::main_parse_arguments;
+use toml_edit::{Array, Document, value};
+
+/// Companion registers are detected by suffixes separated by `_` or by `alternateRegister`.
+/// Registers with names ending like a suffix are not companions, e.g. `RESET` of `RE`.
+#[test]
+fn alias_registers_by_name() {
+    let xml_path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/resources/alias/alias_registers.xml"
+    );
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+        "--alias-registers",
+    ];
+    main_parse_arguments(args);
+
+    let port = fs::read_to_string(generated_code_folder.path().join("src/port.rs")).unwrap();
+    let alias_impls: Vec<&str> = port
+        .lines()
+        .filter(|line| line.contains("AliasRegSpec for"))
+        .map(str::trim)
+        .collect();
+    assert_eq!(
+        alias_impls,
+        [
+            "impl crate::sealed::SetAliasRegSpec for Out_SPEC {",
+            "impl crate::sealed::ClearAliasRegSpec for Out_SPEC {",
+            "impl crate::sealed::ClearAliasRegSpec for Status_SPEC {",
+        ]
+    );
+
+    // Writable companion is enough to clear bits of read-only `STATUS`
+    let lib_path = generated_code_folder.path().join("src/lib.rs");
+    let mut lib = fs::read_to_string(&lib_path).unwrap();
+    lib.push_str("\npub fn clear_status() {\n    unsafe { PORT.status().clear_bits(0x1) };\n}\n");
+    fs::write(&lib_path, lib).unwrap();
+    let toml_path = generated_code_folder.path().join("Cargo.toml");
+    let mut parsed_toml = fs::read_to_string(&toml_path)
+        .unwrap()
+        .parse::<Document>()
+        .expect("Unable to parse toml file");
+    let mut default_table = Array::new();
+    default_table.push("all");
+    parsed_toml["features"]["default"] = value(default_table);
+    fs::write(&toml_path, parsed_toml.to_string()).unwrap();
+
+    assert_cargo_command(&generated_code_folder, CargoCommand::Build, None);
+}
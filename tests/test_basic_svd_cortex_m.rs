@@ -22,6 +22,7 @@ fn compile_generated_cortex_m() {
     let args = [
         "",
         "--target=cortex-m",
+        "--bit-band",
         xml_path,
         generated_pack_folder.to_str().unwrap(),
     ];
@@ -32,3 +33,28 @@ fn compile_generated_cortex_m() {
     assert_cargo_command(&workspace_folder, CargoCommand::Build, None);
     assert_cargo_command(&workspace_folder, CargoCommand::Clippy, None);
 }
+
+/// Bit-band accessors are generated only if all registers of a peripheral, including
+/// registers of clusters, are in the bit-band region.
+#[test]
+fn bit_band_region_of_registers() {
+    let xml_path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/resources/bit_band/bit_band_region.xml"
+    );
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        "--target=cortex-m",
+        "--bit-band",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+    ];
+    main_parse_arguments(args);
+
+    let src_folder = generated_code_folder.path().join("src");
+    let inside = std::fs::read_to_string(src_folder.join("inside.rs")).unwrap();
+    assert!(inside.contains("pub fn en_bb(&self)"));
+    let edge = std::fs::read_to_string(src_folder.join("edge.rs")).unwrap();
+    assert!(!edge.contains("_bb(&self)"));
+}
@@ -13,7 +13,13 @@ fn compile_generated_generic() {
 
     // Temp folder that should be deleted in case of test success.
     let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
-    let args = ["", xml_path, generated_code_folder.path().to_str().unwrap()];
+    let args = [
+        "",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+        "--alias-registers",
+        "--defmt",
+    ];
     main_parse_arguments(args);
 
     //Patch toml and add required files.
@@ -24,6 +30,8 @@ fn compile_generated_generic() {
         .expect("Unable to parse toml file");
     let mut default_table = Array::new();
     default_table.push("all");
+    default_table.push("defmt");
+    default_table.push("metadata");
     parsed_toml["features"]["default"] = value(default_table);
     parsed_toml["bin"] = array();
     let bin_array = parsed_toml["bin"].as_array_of_tables_mut().unwrap();
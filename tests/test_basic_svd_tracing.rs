@@ -19,6 +19,8 @@ fn compile_generated_tracing() {
         xml_path,
         generated_code_folder.path().to_str().unwrap(),
         "--tracing",
+        "--alias-registers",
+        "--serde",
     ];
 
     main_parse_arguments(args);
@@ -32,7 +34,12 @@ fn compile_generated_tracing() {
     let mut default_table = Array::new();
     default_table.push("all");
     default_table.push("tracing");
+    default_table.push("debug");
+    default_table.push("serde");
+    default_table.push("metadata");
+    default_table.push("dump");
     parsed_toml["features"]["default"] = value(default_table);
+    parsed_toml["dev-dependencies"]["serde_json"] = value("1.0");
     parsed_toml["bin"] = array();
     let bin_array = parsed_toml["bin"].as_array_of_tables_mut().unwrap();
 
@@ -80,3 +87,125 @@ fn compile_generated_tracing() {
         Some(env!("AURIX_TOOLCHAIN").to_string()),
     );
 }
+
+/// Test that a PAC generated with tracing support compiles with `metadata` but without `tracing` feature.
+#[test]
+fn compile_generated_metadata_without_tracing() {
+    let xml_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_svd/simple.xml");
+
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+        "--tracing",
+    ];
+    main_parse_arguments(args);
+
+    //Patch toml to enable features without tracing.
+    let old_toml = fs::read_to_string(Path::new(&generated_code_folder.path().join("Cargo.toml")))
+        .expect("Unable to read toml file");
+    let mut parsed_toml = old_toml
+        .parse::<Document>()
+        .expect("Unable to parse toml file");
+    let mut default_table = Array::new();
+    default_table.push("all");
+    default_table.push("metadata");
+    default_table.push("dump");
+    parsed_toml["features"]["default"] = value(default_table);
+    fs::write(
+        Path::new(&generated_code_folder.path().join("Cargo.toml")),
+        parsed_toml.to_string(),
+    )
+    .expect("Unable to write toml file");
+
+    assert_cargo_command(&generated_code_folder, CargoCommand::Build, None);
+    assert_cargo_command(&generated_code_folder, CargoCommand::Clippy, None);
+}
+
+/// Test tracing of Aurix core special function registers on host.
+#[test]
+fn test_generated_aurix_tracing() {
+    let xml_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_svd/simple.xml");
+
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        "--target=aurix",
+        "--tracing",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+    ];
+    main_parse_arguments(args);
+
+    //Patch toml and add required files.
+    let old_toml = fs::read_to_string(Path::new(&generated_code_folder.path().join("Cargo.toml")))
+        .expect("Unable to read toml file");
+    let mut parsed_toml = old_toml
+        .parse::<Document>()
+        .expect("Unable to parse toml file");
+    let mut default_table = Array::new();
+    default_table.push("all");
+    default_table.push("tracing");
+    default_table.push("csfr_cpu0");
+    parsed_toml["features"]["default"] = value(default_table);
+    fs::write(
+        Path::new(&generated_code_folder.path().join("Cargo.toml")),
+        parsed_toml.to_string(),
+    )
+    .expect("Unable to write toml file");
+
+    fs_extra::dir::copy(
+        "./tests/resources/project_files_aurix_tracing",
+        generated_code_folder.path(),
+        &CopyOptions::new().content_only(true).overwrite(true),
+    )
+    .expect("Failed to copy required files to build cargo project");
+
+    assert_cargo_test(&generated_code_folder, None);
+}
+
+/// Test tracing of Cortex-M bit-band accessors on host.
+#[test]
+fn test_generated_cortex_m_bit_band_tracing() {
+    let xml_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_svd/simple.xml");
+
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        "--target=cortex-m",
+        "--bit-band",
+        "--tracing",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+    ];
+    main_parse_arguments(args);
+
+    //Patch toml and add required files.
+    let old_toml = fs::read_to_string(Path::new(&generated_code_folder.path().join("Cargo.toml")))
+        .expect("Unable to read toml file");
+    let mut parsed_toml = old_toml
+        .parse::<Document>()
+        .expect("Unable to parse toml file");
+    let mut default_table = Array::new();
+    default_table.push("all");
+    default_table.push("tracing");
+    parsed_toml["features"]["default"] = value(default_table);
+    fs::write(
+        Path::new(&generated_code_folder.path().join("Cargo.toml")),
+        parsed_toml.to_string(),
+    )
+    .expect("Unable to write toml file");
+
+    fs_extra::dir::copy(
+        "./tests/resources/project_files_cortex_m_tracing",
+        generated_code_folder.path(),
+        &CopyOptions::new().content_only(true).overwrite(true),
+    )
+    .expect("Failed to copy required files to build cargo project");
+
+    assert_cargo_test(&generated_code_folder, None);
+}
@@ -0,0 +1,83 @@
+use std::env;
+use std::process::Command;
+use svd2pac::main_parse_arguments;
+
+/// Test generation of CMSIS-style C header.
+#[test]
+fn generate_c_header() {
+    let xml_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_svd/simple.xml");
+
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+        "--target",
+        "cortex-m",
+        "--emit",
+        "c-header",
+    ];
+    main_parse_arguments(args);
+
+    let header_path = generated_code_folder.path().join("Test_pac.h");
+    assert!(header_path.exists(), "Not found Test_pac.h");
+    assert!(
+        !generated_code_folder.path().join("Cargo.toml").exists(),
+        "Rust package generated without --emit rust"
+    );
+
+    // Check layout of structs with a C compiler if available
+    let output = match Command::new("cc")
+        .args([
+            "-std=c11",
+            "-Wall",
+            "-Wextra",
+            "-Werror",
+            "-fsyntax-only",
+            "-I",
+        ])
+        .arg(generated_code_folder.path())
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/resources/c_header/check_layout.c"
+        ))
+        .output()
+    {
+        Ok(output) => output,
+        Err(_) => {
+            eprintln!("C compiler not found. Layout of generated header not checked");
+            return;
+        }
+    };
+    assert!(
+        output.status.success(),
+        "Failed to compile generated header:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn generate_rust_and_c_header() {
+    let xml_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_svd/simple.xml");
+
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+        "--emit",
+        "rust,c-header",
+    ];
+    main_parse_arguments(args);
+
+    assert!(generated_code_folder.path().join("Cargo.toml").exists());
+    let header = std::fs::read_to_string(generated_code_folder.path().join("Test_pac.h"))
+        .expect("Unable to read generated header");
+    // Core exceptions are defined only for cortex-m
+    assert!(!header.contains("SysTick_IRQn"));
+    assert!(header.contains("TIMER0_IRQn = 0,"));
+    // Derived register has the bitfields of its base register
+    assert!(header.contains("#define DerivedTest_DerivedRegister_RUN_Pos (0UL)"));
+}
@@ -0,0 +1,156 @@
+use std::process::{Command, Output};
+
+const OLD_SVD: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resources/diff/old.xml");
+const NEW_SVD: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resources/diff/new.xml");
+
+fn svd2pac_diff(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_svd2pac"))
+        .arg("diff")
+        .args(args)
+        .output()
+        .expect("Failed to execute svd2pac")
+}
+
+/// Comparing a file with itself reports no change and exits with success.
+#[test]
+fn diff_identical_svd() {
+    let output = svd2pac_diff(&[OLD_SVD, OLD_SVD]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), "0 changes, 0 breaking");
+}
+
+/// Breaking changes are reported and exit code is 1.
+#[test]
+fn diff_text() {
+    let output = svd2pac_diff(&[OLD_SVD, NEW_SVD]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    let expected = [
+        "> moved peripheral TIMER: address 0x40010000 -> 0x40011000",
+        "- removed peripheral OLDP [breaking]",
+        "+ added peripheral NEWP",
+        "~ modified register TIMER.CR: reset_value 0x0 -> 0x2",
+        "~ modified register TIMER.SR: access read-only -> read-write",
+        "- removed register TIMER.DATA [breaking]",
+        "+ added register TIMER.NEWREG",
+        "> moved field TIMER.CR.EN: bit_offset 0 -> 3",
+        "- removed enum value TIMER.CR.MODE.C [breaking]",
+        "+ added enum value TIMER.CR.MODE.D",
+        "10 changes, 3 breaking",
+    ];
+    assert_eq!(lines, expected);
+
+    // Registers of removed peripheral are not reported
+    assert!(!stdout.contains("OLDP.REG"));
+}
+
+/// Reverse comparison: added items become removed items.
+#[test]
+fn diff_json() {
+    let output = svd2pac_diff(&["--format", "json", NEW_SVD, OLD_SVD]);
+    assert_eq!(output.status.code(), Some(1));
+    let diff: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+    assert_eq!(diff["breaking"], true);
+    let changes = diff["changes"].as_array().unwrap();
+    assert_eq!(changes.len(), 10);
+    let removed_register = changes
+        .iter()
+        .find(|change| change["path"] == "TIMER.NEWREG")
+        .expect("Removed register not reported");
+    assert_eq!(removed_register["kind"], "removed");
+    assert_eq!(removed_register["item"], "register");
+    assert_eq!(removed_register["breaking"], true);
+    let access = changes
+        .iter()
+        .find(|change| change["path"] == "TIMER.SR")
+        .unwrap();
+    assert_eq!(access["property"], "access");
+    assert_eq!(access["old"], "read-write");
+    assert_eq!(access["new"], "read-only");
+    assert_eq!(access["breaking"], true);
+}
+
+/// Derived registers are compared with the bitfields of their base register.
+#[test]
+fn diff_derived_registers() {
+    let output = svd2pac_diff(&[
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/resources/diff/derived_old.xml"
+        ),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/resources/diff/derived_new.xml"
+        ),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    let expected = [
+        "> moved field TIMER.CR.EN: bit_offset 0 -> 3",
+        // Changes of the base register apply to derived registers
+        "> moved field TIMER.CR2.EN: bit_offset 0 -> 3",
+        // Derived register that defines its own bitfields
+        "- removed field TIMER.CR3.MODE [breaking]",
+        "3 changes, 1 breaking",
+    ];
+    assert_eq!(lines, expected);
+}
+
+/// Lines of the text output of the comparison of `<name>_old.xml` with `<name>_new.xml` in `tests/resources/diff`
+fn diff_resources(name: &str) -> Vec<String> {
+    let resource = |version: &str| {
+        format!(
+            "{}/tests/resources/diff/{name}_{version}.xml",
+            env!("CARGO_MANIFEST_DIR")
+        )
+    };
+    let output = svd2pac_diff(&[&resource("old"), &resource("new")]);
+    assert_eq!(output.status.code(), Some(1));
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Removed indexes and renamed or removed clusters change the accessors of clusters.
+#[test]
+fn diff_clusters() {
+    let expected = [
+        "~ modified cluster TIMER.CH: dim 4 -> 2 [breaking]",
+        "~ modified cluster TIMER.PORT: dim_index PORTA,PORTB -> PORTA,PORTC [breaking]",
+        "~ modified cluster TIMER.GROW: dim 2 -> 4",
+        "- removed cluster TIMER.OLDC [breaking]",
+        "+ added cluster TIMER.NEWC",
+        "5 changes, 3 breaking",
+    ];
+    assert_eq!(diff_resources("clusters"), expected);
+}
+
+/// Removed indexes of arrays of registers and bitfields are breaking, added indexes are not.
+#[test]
+fn diff_dim() {
+    let expected = [
+        "~ modified register TIMER.DATA: dim 4 -> 2 [breaking]",
+        "~ modified register TIMER.REG: dim_index REGA,REGB -> REGA,REGC [breaking]",
+        "~ modified register TIMER.BUF: dim 2 -> 4",
+        "~ modified field TIMER.CR.EN: dim 4 -> 2 [breaking]",
+        "4 changes, 3 breaking",
+    ];
+    assert_eq!(diff_resources("dim"), expected);
+}
+
+/// Removed interrupts remove variants of `Interrupt` enum.
+#[test]
+fn diff_interrupts() {
+    let expected = [
+        "~ modified interrupt UART_IRQ: value 6 -> 8",
+        "- removed interrupt OLD_IRQ [breaking]",
+        "+ added interrupt NEW_IRQ",
+        "3 changes, 1 breaking",
+    ];
+    assert_eq!(diff_resources("interrupts"), expected);
+}
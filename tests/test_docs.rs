@@ -0,0 +1,67 @@
+use std::env;
+use std::fs;
+use svd2pac::main_parse_arguments;
+
+/// Test generation of HTML register manual.
+#[test]
+fn generate_docs() {
+    let xml_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_svd/simple.xml");
+
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+        "--emit",
+        "docs",
+    ];
+    main_parse_arguments(args);
+
+    let docs_folder = generated_code_folder.path().join("docs");
+    let read_page = |page: &str| {
+        fs::read_to_string(docs_folder.join(page))
+            .unwrap_or_else(|_| panic!("Unable to read {page}"))
+    };
+
+    let index = read_page("index.html");
+    assert!(index.contains(r#"<a href="timer.html#reg-SR">SR</a>"#));
+    assert!(index.contains(r#"<td><a href="p33.html">P33</a></td>"#));
+    // Interrupt list links the peripheral that defines the interrupt
+    assert!(index.contains("<td>TIMER0</td>\n<td><a href=\"timer.html\">TIMER</a></td>"));
+
+    let timer = read_page("timer.html");
+    assert!(timer.contains(r#"<div class="register" id="reg-SR">"#));
+    // Address map contains absolute addresses and arrays
+    assert!(timer.contains(r#"<td class="mono">0x40010004</td>"#));
+    assert!(timer.contains("<small>i = 0..3, step 0x100</small>"));
+    // Bit diagram of SR
+    assert!(timer.contains(
+        r#"<td colspan="2">RELOAD</td><td colspan="1" class="reserved"></td><td colspan="1">RST</td>"#
+    ));
+    // Reset value with reset mask 0xD701
+    assert!(timer.contains(r#"<tr><th>Reset value</th><td class="mono">0x????</td></tr>"#));
+    // Enumerated values
+    assert!(timer.contains("<td>Match_Hit</td><td>The MATCH condition was hit</td>"));
+
+    // Cross-links of derived peripherals
+    assert!(
+        read_page("p33.html").contains(r#"<a href="derivedperipheral.html">DerivedPeripheral</a>"#)
+    );
+    assert!(
+        read_page("derivedperipheral.html").contains(r#"Derived from <a href="p33.html">P33</a>."#)
+    );
+
+    // Derived register shows the bitfields of its base register
+    let derived_test = read_page("derivedtest.html");
+    let derived_register = derived_test
+        .split(r#"<div class="register" id="reg-DerivedRegister">"#)
+        .nth(1)
+        .and_then(|page| page.split(r#"<div class="register""#).next())
+        .expect("DerivedRegister not documented");
+    assert!(derived_register.contains(r#"<td colspan="1">RUN</td>"#));
+    assert!(derived_register.contains("<td>Running</td><td>Timer is running</td>"));
+
+    // Descriptions are escaped
+    assert!(read_page("escapetest.html").contains("[]\\&quot;"));
+}
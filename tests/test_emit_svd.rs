@@ -0,0 +1,81 @@
+use std::env;
+use std::fs;
+use svd2pac::main_parse_arguments;
+
+/// Test generation of normalized SVD file.
+#[test]
+fn emit_normalized_svd() {
+    let xml_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_svd/simple.xml");
+
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let svd_path = generated_code_folder.path().join("normalized.svd");
+    let args = [
+        "",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+        "--emit-svd",
+        svd_path.to_str().unwrap(),
+    ];
+    main_parse_arguments(args);
+    // PAC is generated by default if destination folder is given
+    assert!(
+        generated_code_folder.path().join("Cargo.toml").exists(),
+        "PAC not generated"
+    );
+
+    let svd = fs::read_to_string(&svd_path).expect("Unable to read normalized svd");
+    assert!(!svd.contains("derivedFrom="), "derivedFrom not resolved");
+    assert!(!svd.contains("<dim>"), "Arrays not expanded");
+    assert!(svd.contains("<name>ARRAYREG3</name>"));
+    assert!(svd.contains("<name>UART2</name>"));
+    assert!(!svd.contains("<bitRange>") && !svd.contains("<lsb>"));
+
+    // Normalization of a normalized file shall not change it
+    let renormalized_path = generated_code_folder.path().join("renormalized.svd");
+    let args = [
+        "",
+        svd_path.to_str().unwrap(),
+        generated_code_folder.path().to_str().unwrap(),
+        "--svd-validation-level",
+        "strict",
+        "--emit-svd",
+        renormalized_path.to_str().unwrap(),
+    ];
+    main_parse_arguments(args);
+    let renormalized_svd =
+        fs::read_to_string(&renormalized_path).expect("Unable to read renormalized svd");
+    assert_eq!(svd, renormalized_svd);
+}
+
+/// Normalized SVD can be generated without destination folder and without PAC.
+#[test]
+fn emit_svd_without_destination_folder() {
+    let xml_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_svd/simple.xml");
+
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let svd_path = generated_code_folder.path().join("normalized.svd");
+    let args = ["", xml_path, "--emit-svd", svd_path.to_str().unwrap()];
+    main_parse_arguments(args);
+    assert!(svd_path.exists(), "Normalized SVD not generated");
+
+    let header_folder = generated_code_folder.path().join("header");
+    let args = [
+        "",
+        xml_path,
+        header_folder.to_str().unwrap(),
+        "--emit",
+        "c-header",
+        "--emit-svd",
+        svd_path.to_str().unwrap(),
+    ];
+    main_parse_arguments(args);
+    assert!(
+        header_folder.join("Test_pac.h").exists(),
+        "C header not generated"
+    );
+    assert!(
+        !header_folder.join("Cargo.toml").exists(),
+        "PAC generated without being selected by --emit"
+    );
+}
@@ -0,0 +1,74 @@
+use std::process::{Command, Output};
+
+fn svd2pac(args: &[&str]) -> Output {
+    let destination_folder = tempfile::tempdir().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_svd2pac"))
+        .args(args)
+        .arg(destination_folder.path())
+        .env("SVD2PAC_LOG_STYLE", "never")
+        .output()
+        .expect("Failed to execute svd2pac")
+}
+
+/// Error detected while converting the SVD reports path of array elements, position and XML of the item.
+#[test]
+fn error_location_of_register() {
+    let xml_path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/resources/errors/missing_size.xml"
+    );
+    let output = svd2pac(&[xml_path]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains(
+            "Invalid register \"CR1\": \"register is not derived and it has no specified size\""
+        ),
+        "{stderr}"
+    );
+    assert!(stderr.contains("missing_size.xml:36:11"), "{stderr}");
+    assert!(stderr.contains("36 |           <register>"), "{stderr}");
+    assert!(
+        stderr.contains("37 |             <name>CR1</name>"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("|             ^^^^^^^^^^^^^^^^ TIMER0.CH0.CR1"),
+        "{stderr}"
+    );
+}
+
+/// Error detected by svd-parser validation reports position of the item.
+#[test]
+fn error_location_of_svd_parser_error() {
+    let xml_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resources/lint/lint.xml");
+    let output = svd2pac(&["--svd-validation-level", "strict", xml_path]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Value 4 out of range"), "{stderr}");
+    assert!(stderr.contains("lint.xml:39:13"), "{stderr}");
+    assert!(stderr.contains("39 |             <field>"), "{stderr}");
+    assert!(stderr.contains("TIMER.CR.MODE"), "{stderr}");
+}
+
+/// Error detected by svd-parser in a cluster reports the path including the cluster.
+///
+/// The path is built from the context of svd-parser errors. This test fails if svd-parser changes the format of its context.
+#[test]
+fn error_location_of_svd_parser_error_in_cluster() {
+    let xml_path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/resources/errors/svd_parser_cluster.xml"
+    );
+    let output = svd2pac(&["--svd-validation-level", "strict", xml_path]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Value 4 out of range"), "{stderr}");
+    assert!(stderr.contains("svd_parser_cluster.xml:34:15"), "{stderr}");
+    assert!(stderr.contains("34 |               <field>"), "{stderr}");
+    assert!(
+        stderr.contains("35 |                 <name>MODE</name>"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("TIMER0.CH[%s].CR.MODE"), "{stderr}");
+}
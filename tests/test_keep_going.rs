@@ -0,0 +1,89 @@
+mod common;
+use common::*;
+use std::env;
+use std::fs;
+use std::process::Command;
+use svd2pac::main_parse_arguments;
+
+const XML_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/resources/errors/multiple_errors.xml"
+);
+
+/// All errors are reported together.
+#[test]
+fn report_all_errors() {
+    let destination_folder = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_svd2pac"))
+        .arg(XML_PATH)
+        .arg(destination_folder.path())
+        .env("SVD2PAC_LOG_STYLE", "never")
+        .output()
+        .expect("Failed to execute svd2pac");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("4 errors in SVD file"), "{stderr}");
+    for svd_path in ["^ TIMER.CR.START", "^ TIMER.SR", "^ TIMER.DR", "^ UART"] {
+        assert!(stderr.contains(svd_path), "{svd_path} missing in {stderr}");
+    }
+    assert!(!destination_folder.path().join("src").exists());
+}
+
+/// With `--keep-going` items with errors are omitted and the generated PAC compiles.
+#[test]
+fn keep_going_omits_items_with_errors() {
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        XML_PATH,
+        generated_code_folder.path().to_str().unwrap(),
+        "--keep-going",
+        "--tracing",
+    ];
+    main_parse_arguments(args);
+
+    let src_folder = generated_code_folder.path().join("src");
+    assert!(src_folder.join("gpio.rs").exists());
+    assert!(!src_folder.join("uart.rs").exists());
+    let timer = fs::read_to_string(src_folder.join("timer.rs")).unwrap();
+    assert!(timer.contains("pub const fn cr(&self)"));
+    assert!(timer.contains("pub fn en(self)"));
+    assert!(!timer.contains("pub fn start(self)"));
+    assert!(!timer.contains("pub const fn sr(&self)"));
+    assert!(!timer.contains("pub const fn dr(&self)"));
+    let reg_name = fs::read_to_string(src_folder.join("reg_name.rs")).unwrap();
+    assert!(reg_name.contains("0x40010000u64 =>"));
+    assert!(!reg_name.contains("0x40010004u64 =>"));
+    assert!(!reg_name.contains("0x40010008u64 =>"));
+    assert!(!reg_name.contains("UART"));
+
+    assert_cargo_command(&generated_code_folder, CargoCommand::Build, None);
+}
+
+/// With `--keep-going` core special function registers with errors are omitted too.
+#[test]
+fn keep_going_omits_csfr_with_errors() {
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/resources/errors/csfr_errors.xml"
+        ),
+        generated_code_folder.path().to_str().unwrap(),
+        "--target=aurix",
+        "--keep-going",
+        "--tracing",
+    ];
+    main_parse_arguments(args);
+
+    let src_folder = generated_code_folder.path().join("src");
+    let csfr = fs::read_to_string(src_folder.join("csfr_cpu0.rs")).unwrap();
+    assert!(csfr.contains("pub const fn pcxi(&self)"));
+    assert!(!csfr.contains("pub const fn psw(&self)"));
+    let reg_name = fs::read_to_string(src_folder.join("reg_name.rs")).unwrap();
+    assert!(reg_name.contains("0xfe00u16 =>"));
+    assert!(!reg_name.contains("0xfe04u16 =>"));
+}
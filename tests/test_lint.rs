@@ -0,0 +1,86 @@
+use std::process::{Command, Output};
+
+const LINT_SVD: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resources/lint/lint.xml");
+
+fn svd2pac_lint(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_svd2pac"))
+        .arg("lint")
+        .args(args)
+        .output()
+        .expect("Failed to execute svd2pac")
+}
+
+/// Files used to test code generation have no error.
+#[test]
+fn lint_simple_svd() {
+    let output = svd2pac_lint(&[concat!(env!("CARGO_MANIFEST_DIR"), "/test_svd/simple.xml")]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), "0 errors, 0 warnings");
+}
+
+/// All checks are executed and errors set the exit code.
+#[test]
+fn lint_text() {
+    // Value out of range of enumerated value is rejected by svd-parser validation
+    let output = svd2pac_lint(&["--svd-validation-level", "disabled", LINT_SVD]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    let expected = [
+        "warning[missing-license] LintTest: no license defined. Use --license-file option when generating the PAC",
+        "warning[sanitized-name] TIMER.DATA-IN: invalid characters in name are replaced: `DATA_IN`",
+        "error[duplicate-name] TIMER.DATA_IN: generates the same identifier as `DATA-IN`",
+        "error[overlapping-fields] TIMER.CR.MODE: overlaps bitfield `EN`",
+        "error[enum-value-out-of-range] TIMER.CR.MODE.FAST: value 0x4 doesn't fit in 2 bits",
+        "error[field-out-of-range] TIMER.CR.TOP: bits 30..34 exceed register size of 32 bits",
+        "warning[inferred-access] TIMER.NOACC: no access defined, access is inferred from bitfields",
+        "warning[missing-access] TIMER.NOACC.VALUE: no access defined, bitfield is skipped",
+        "warning[overlapping-registers] TIMER.SR: overlaps register `TIMER.CR` at address 0x40010002 and is not declared as alternate register",
+        "warning[duplicated-interrupt] UART.UART_IRQ: interrupt number 5 is already used by `TIMER_IRQ`",
+        "4 errors, 6 warnings",
+    ];
+    assert_eq!(lines, expected);
+}
+
+#[test]
+fn lint_json() {
+    let output = svd2pac_lint(&[
+        "--svd-validation-level",
+        "disabled",
+        "--format",
+        "json",
+        LINT_SVD,
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+    assert_eq!(report["errors"], 4);
+    assert_eq!(report["warnings"], 6);
+    let diagnostic = report["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|diagnostic| diagnostic["code"] == "overlapping-fields")
+        .expect("Overlapping fields not reported");
+    assert_eq!(diagnostic["severity"], "error");
+    assert_eq!(diagnostic["path"], "TIMER.CR.MODE");
+}
+
+/// Problems of arrays and derived peripherals are reported once with the SVD path as written.
+#[test]
+fn lint_arrays() {
+    let output = svd2pac_lint(&[concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/resources/lint/lint_arrays.xml"
+    )]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    let expected = [
+        "warning[missing-license] LintArrays: no license defined. Use --license-file option when generating the PAC",
+        "error[overlapping-fields] PWM[%s].CR%s.MODE: overlaps bitfield `EN`",
+        "error[field-out-of-range] TIMER0.CR.TOP: bits 30..34 exceed register size of 32 bits",
+        "2 errors, 1 warnings",
+    ];
+    assert_eq!(lines, expected);
+}
@@ -0,0 +1,45 @@
+use std::env;
+use std::fs;
+use svd2pac::main_parse_arguments;
+
+/// Aurix interrupts are linked to the SRC register with the same name as the interrupt.
+/// Cluster names, array indices, `_` and the `SRC` prefix are considered.
+#[test]
+fn service_request_nodes_of_interrupts() {
+    let xml_path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/resources/aurix/service_request_nodes.xml"
+    );
+    // Temp folder that should be deleted in case of test success.
+    let generated_code_folder = tempfile::tempdir_in(env::current_dir().unwrap()).unwrap();
+    let args = [
+        "",
+        "--target=aurix",
+        xml_path,
+        generated_code_folder.path().to_str().unwrap(),
+    ];
+    main_parse_arguments(args);
+
+    let lib = fs::read_to_string(generated_code_folder.path().join("src/lib.rs")).unwrap();
+    let src_addr = lib
+        .split("pub const fn src_addr(self) -> Option<usize> {")
+        .nth(1)
+        .expect("src_addr not generated");
+    let arms: Vec<&str> = src_addr
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("Self::"))
+        .collect();
+    assert_eq!(
+        arms,
+        [
+            // Cluster `TIMER0` with register `CMP`
+            "Self::TIMER0_CMP => Some(0xf0038004),",
+            // Register `SRC_GPT` without cluster
+            "Self::GPT => Some(0xf0038010),",
+            // Second element of cluster array `CAN[%s]`
+            "Self::CAN1_INT => Some(0xf0038024),",
+            "Self::UART_RX => None,",
+        ]
+    );
+}
@@ -0,0 +1,48 @@
+use std::fs;
+use std::process::{Command, Output};
+
+fn svd2pac_aurix(svd: &str, destination_folder: &std::path::Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_svd2pac"))
+        .arg("--target")
+        .arg("aurix")
+        .arg(svd)
+        .arg(destination_folder)
+        .env("SVD2PAC_LOG_STYLE", "never")
+        .output()
+        .expect("Failed to execute svd2pac")
+}
+
+/// Namespaced and single line vendor extensions are parsed, comments and unknown extensions are ignored.
+#[test]
+fn aurix_csfr_namespaced() {
+    let destination_folder = tempfile::tempdir().unwrap();
+    let output = svd2pac_aurix(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/resources/vendor_extensions/csfr.xml"
+        ),
+        destination_folder.path(),
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("Vendor extension <unknownExtension> is not supported"));
+    let csfr = fs::read_to_string(destination_folder.path().join("src/csfr_cpu0.rs")).unwrap();
+    assert!(csfr.contains("pub const fn pcxi(&self)"));
+}
+
+/// Errors in core special function registers point to the invalid element.
+#[test]
+fn aurix_csfr_invalid_field() {
+    let destination_folder = tempfile::tempdir().unwrap();
+    let output = svd2pac_aurix(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/resources/vendor_extensions/invalid_csfr.xml"
+        ),
+        destination_folder.path(),
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("invalid_csfr.xml:45:15"), "{stderr}");
+    assert!(stderr.contains("^^^^^^^ CPU0.PCXI.PCXO"), "{stderr}");
+}